@@ -9,9 +9,9 @@ use std::{
 
 use crate::TIndex;
 
-type Frame = u64;
+pub(crate) type Frame = u64;
 
-const FRAME_SIZE: usize = mem::size_of::<Frame>() * 8;
+pub(crate) const FRAME_SIZE: usize = mem::size_of::<Frame>() * 8;
 
 pub struct TBitSet<I> {
     _marker: PhantomData<fn(I)>,
@@ -81,6 +81,14 @@ impl<I> TBitSet<I> {
         }
     }
 
+    /// Reinterprets `self` as a set of `J`, see [`TVec::cast_index`](crate::TVec::cast_index).
+    pub fn cast_index<J>(self) -> TBitSet<J> {
+        TBitSet {
+            _marker: PhantomData,
+            inner: self.inner,
+        }
+    }
+
     pub fn frame_count(&self) -> usize {
         self.inner.len()
     }
@@ -101,7 +109,7 @@ impl<I> TBitSet<I> {
     }
 
     pub fn shrink_to_fit(&mut self) {
-        while self.inner.last().map_or(false, |&l| l == 0) {
+        while self.inner.last().is_some_and(|&l| l == 0) {
             self.inner.pop();
         }
     }
@@ -148,12 +156,12 @@ impl<I> TBitSet<I> {
         if frame_offset >= self.inner.len() {
             if value {
                 self.inner.resize(frame_offset + 1, 0);
-                self.inner[frame_offset] |= 1 << idx - frame_offset * FRAME_SIZE;
+                self.inner[frame_offset] |= 1 << (idx - frame_offset * FRAME_SIZE);
             }
         } else if value {
-            self.inner[frame_offset] |= 1 << idx - frame_offset * FRAME_SIZE;
+            self.inner[frame_offset] |= 1 << (idx - frame_offset * FRAME_SIZE);
         } else {
-            self.inner[frame_offset] &= !(1 << idx - frame_offset * FRAME_SIZE);
+            self.inner[frame_offset] &= !(1 << (idx - frame_offset * FRAME_SIZE));
         }
     }
 }
@@ -177,7 +185,7 @@ impl<I: TIndex> TBitSet<I> {
             self.inner.resize(frame_offset + 1, 0);
         }
 
-        self.inner[frame_offset] ^= 1 << idx - frame_offset * FRAME_SIZE;
+        self.inner[frame_offset] ^= 1 << (idx - frame_offset * FRAME_SIZE);
     }
 
     pub fn flip(&mut self, idx: I) {
@@ -189,7 +197,7 @@ impl<I: TIndex> TBitSet<I> {
         let frame_offset = idx / FRAME_SIZE;
         self.inner
             .get(frame_offset)
-            .map_or(false, |v| v & (1 << idx - frame_offset * FRAME_SIZE) != 0)
+            .is_some_and(|v| v & (1 << (idx - frame_offset * FRAME_SIZE)) != 0)
     }
 
     #[inline]
@@ -262,7 +270,8 @@ impl<I: TIndex, B: Borrow<TBitSet<I>>> Iterator for Iter<I, B> {
             let pos = self.pos;
             self.pos += 1;
             if self.bitset.borrow().get_usize(pos) {
-                return Some(I::from_index(pos.into()));
+                debug_assert!(pos <= I::MAX_INDEX, "index overflow in `TBitSet::iter`");
+                return Some(I::from_index(pos));
             }
         }
         None
@@ -285,6 +294,10 @@ impl<I: TIndex, B: Borrow<TBitSet<I>>> DoubleEndedIterator for Iter<I, B> {
         while self.end_pos > self.pos {
             self.end_pos -= 1;
             if self.bitset.borrow().get_usize(self.end_pos) {
+                debug_assert!(
+                    self.end_pos <= I::MAX_INDEX,
+                    "index overflow in `TBitSet::iter`"
+                );
                 return Some(I::from_index(self.end_pos));
             }
         }
@@ -294,6 +307,7 @@ impl<I: TIndex, B: Borrow<TBitSet<I>>> DoubleEndedIterator for Iter<I, B> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -0,0 +1,328 @@
+//! Chunk and split iterators which yield each piece together with the
+//! index of its first element.
+use std::{
+    iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator},
+    marker::PhantomData,
+    slice,
+};
+
+use crate::{TIndex, TSlice};
+
+/// The part of the slice which has not yet been yielded.
+#[derive(Debug, Clone)]
+struct Bounds {
+    front: usize,
+    back: usize,
+}
+
+impl Bounds {
+    /// Consumes `advance` elements at the front followed by `gap` separators,
+    /// returning the start of the consumed elements.
+    fn take_front(&mut self, advance: usize, gap: usize) -> usize {
+        let start = self.front;
+        self.front = start + advance + gap;
+        start
+    }
+
+    /// Consumes `advance` elements at the back preceded by `gap` separators,
+    /// returning the start of the consumed elements.
+    fn take_back(&mut self, advance: usize, gap: usize) -> usize {
+        let start = self.back - advance;
+        self.back = start.saturating_sub(gap);
+        start
+    }
+}
+
+macro_rules! based_iter {
+    (
+        $(#[$attr:meta])*
+        $name:ident<'a, I, T $(, $p:ident)?> => $inner:ty,
+        $(where [$($bounds:tt)*],)?
+        advance: |$piece:ident| $advance:expr,
+        gap: $gap:expr,
+        next: $next:ident,
+        next_back: $next_back:ident $(,)?
+    ) => {
+        $(#[$attr])*
+        pub struct $name<'a, I, T $(, $p)?>
+        $(where $($bounds)*)?
+        {
+            _marker: PhantomData<fn(I)>,
+            inner: $inner,
+            bounds: Bounds,
+        }
+
+        impl<'a, I, T $(, $p)?> $name<'a, I, T $(, $p)?>
+        $(where $($bounds)*)?
+        {
+            fn new(inner: $inner, front: usize, back: usize) -> Self {
+                Self {
+                    _marker: PhantomData,
+                    inner,
+                    bounds: Bounds { front, back },
+                }
+            }
+        }
+
+        impl<'a, I, T $(, $p)?> Clone for $name<'a, I, T $(, $p)?>
+        where
+            $inner: Clone,
+            $($($bounds)*)?
+        {
+            fn clone(&self) -> Self {
+                Self {
+                    _marker: PhantomData,
+                    inner: self.inner.clone(),
+                    bounds: self.bounds.clone(),
+                }
+            }
+        }
+
+        impl<'a, I: TIndex + 'a, T $(, $p)?> Iterator for $name<'a, I, T $(, $p)?>
+        $(where $($bounds)*)?
+        {
+            type Item = (I, &'a TSlice<I, T>);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let $piece: &'a [T] = self.inner.next()?;
+                let start = self.bounds.$next($advance, $gap);
+                Some((I::from_index(start), $piece.into()))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl<'a, I: TIndex + 'a, T $(, $p)?> FusedIterator for $name<'a, I, T $(, $p)?>
+        $(where $($bounds)*)?
+        {}
+
+        based_iter!(@double_ended $name<'a, I, T $(, $p)?> => $inner,
+            $(where [$($bounds)*],)?
+            advance: |$piece| $advance,
+            gap: $gap,
+            next_back: $next_back,
+        );
+    };
+    (@double_ended $name:ident<'a, I, T $(, $p:ident)?> => $inner:ty,
+        $(where [$($bounds:tt)*],)?
+        advance: |$piece:ident| $advance:expr,
+        gap: $gap:expr,
+        next_back: none,
+    ) => {};
+    (@double_ended $name:ident<'a, I, T $(, $p:ident)?> => $inner:ty,
+        $(where [$($bounds:tt)*],)?
+        advance: |$piece:ident| $advance:expr,
+        gap: $gap:expr,
+        next_back: $next_back:ident,
+    ) => {
+        impl<'a, I: TIndex + 'a, T $(, $p)?> DoubleEndedIterator for $name<'a, I, T $(, $p)?>
+        $(where $($bounds)*)?
+        {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let $piece: &'a [T] = self.inner.next_back()?;
+                let start = self.bounds.$next_back($advance, $gap);
+                Some((I::from_index(start), $piece.into()))
+            }
+        }
+    };
+}
+
+macro_rules! exact_size {
+    ($($name:ident),*) => {
+        $(
+            impl<'a, I: TIndex + 'a, T> ExactSizeIterator for $name<'a, I, T> {
+                fn len(&self) -> usize {
+                    self.inner.len()
+                }
+            }
+        )*
+    };
+}
+
+based_iter! {
+    /// Created by `TSlice::chunks_enumerated`.
+    Chunks<'a, I, T> => slice::Chunks<'a, T>,
+    advance: |chunk| chunk.len(),
+    gap: 0,
+    next: take_front,
+    next_back: take_back,
+}
+
+based_iter! {
+    /// Created by `TSlice::chunks_exact_enumerated`.
+    ChunksExact<'a, I, T> => slice::ChunksExact<'a, T>,
+    advance: |chunk| chunk.len(),
+    gap: 0,
+    next: take_front,
+    next_back: take_back,
+}
+
+based_iter! {
+    /// Created by `TSlice::rchunks_enumerated`.
+    RChunks<'a, I, T> => slice::RChunks<'a, T>,
+    advance: |chunk| chunk.len(),
+    gap: 0,
+    next: take_back,
+    next_back: take_front,
+}
+
+based_iter! {
+    /// Created by `TSlice::windows_enumerated`.
+    Windows<'a, I, T> => slice::Windows<'a, T>,
+    advance: |window| 1,
+    gap: 0,
+    next: take_front,
+    next_back: take_back,
+}
+
+exact_size!(Chunks, ChunksExact, RChunks, Windows);
+
+based_iter! {
+    /// Created by `TSlice::split_enumerated`.
+    Split<'a, I, T, P> => slice::Split<'a, T, P>,
+    where [P: FnMut(&T) -> bool],
+    advance: |piece| piece.len(),
+    gap: 1,
+    next: take_front,
+    next_back: take_back,
+}
+
+based_iter! {
+    /// Created by `TSlice::splitn_enumerated`.
+    SplitN<'a, I, T, P> => slice::SplitN<'a, T, P>,
+    where [P: FnMut(&T) -> bool],
+    advance: |piece| piece.len(),
+    gap: 1,
+    next: take_front,
+    next_back: none,
+}
+
+based_iter! {
+    /// Created by `TSlice::chunk_by_enumerated`.
+    ChunkBy<'a, I, T, P> => slice::ChunkBy<'a, T, P>,
+    where [P: FnMut(&T, &T) -> bool],
+    advance: |chunk| chunk.len(),
+    gap: 0,
+    next: take_front,
+    next_back: take_back,
+}
+
+impl<I: TIndex, T> TSlice<I, T> {
+    pub fn chunks_enumerated(&self, chunk_size: usize) -> Chunks<'_, I, T> {
+        Chunks::new(self.inner.chunks(chunk_size), 0, self.len())
+    }
+
+    /// Like `chunks_enumerated`, but omits the last chunk if it would be
+    /// smaller than `chunk_size`.
+    pub fn chunks_exact_enumerated(&self, chunk_size: usize) -> ChunksExact<'_, I, T> {
+        let inner = self.inner.chunks_exact(chunk_size);
+        let back = self.len() - inner.remainder().len();
+        ChunksExact::new(inner, 0, back)
+    }
+
+    /// Like `chunks_enumerated`, but starts at the end of the slice.
+    pub fn rchunks_enumerated(&self, chunk_size: usize) -> RChunks<'_, I, T> {
+        RChunks::new(self.inner.rchunks(chunk_size), 0, self.len())
+    }
+
+    pub fn windows_enumerated(&self, size: usize) -> Windows<'_, I, T> {
+        let back = (self.len() + 1).saturating_sub(size);
+        Windows::new(self.inner.windows(size), 0, back)
+    }
+
+    pub fn split_enumerated<P>(&self, pred: P) -> Split<'_, I, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Split::new(self.inner.split(pred), 0, self.len())
+    }
+
+    pub fn splitn_enumerated<P>(&self, n: usize, pred: P) -> SplitN<'_, I, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        SplitN::new(self.inner.splitn(n, pred), 0, self.len())
+    }
+
+    pub fn chunk_by_enumerated<P>(&self, pred: P) -> ChunkBy<'_, I, T, P>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        ChunkBy::new(self.inner.chunk_by(pred), 0, self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tvec, TVec};
+
+    fn collect<'a, It>(iter: It) -> Vec<(u32, &'a [u8])>
+    where
+        It: Iterator<Item = (u32, &'a crate::TSlice<u32, u8>)>,
+    {
+        iter.map(|(i, s)| (i, s.to_slice())).collect()
+    }
+
+    #[test]
+    fn chunks() {
+        let v: TVec<u32, u8> = tvec![0, 1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            collect(v.chunks_enumerated(3)),
+            vec![(0, &[0, 1, 2][..]), (3, &[3, 4, 5]), (6, &[6])]
+        );
+        assert_eq!(
+            collect(v.chunks_enumerated(3).rev()),
+            vec![(6, &[6][..]), (3, &[3, 4, 5]), (0, &[0, 1, 2])]
+        );
+        assert_eq!(
+            collect(v.chunks_exact_enumerated(3).rev()),
+            vec![(3, &[3, 4, 5][..]), (0, &[0, 1, 2])]
+        );
+        assert_eq!(
+            collect(v.rchunks_enumerated(3)),
+            vec![(4, &[4, 5, 6][..]), (1, &[1, 2, 3]), (0, &[0])]
+        );
+        assert_eq!(
+            collect(v.rchunks_enumerated(3).rev()),
+            vec![(0, &[0][..]), (1, &[1, 2, 3]), (4, &[4, 5, 6])]
+        );
+
+        let mut windows = v.windows_enumerated(5);
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows.next_back().map(|(i, _)| i), Some(2));
+        assert_eq!(windows.next().map(|(i, _)| i), Some(0));
+        assert_eq!(collect(windows), vec![(1, &[1, 2, 3, 4, 5][..])]);
+    }
+
+    #[test]
+    fn split() {
+        let v: TVec<u32, u8> = tvec![1, 0, 2, 3, 0, 0, 4];
+        assert_eq!(
+            collect(v.split_enumerated(|&x| x == 0)),
+            vec![(0, &[1][..]), (2, &[2, 3]), (5, &[]), (6, &[4])]
+        );
+        assert_eq!(
+            collect(v.split_enumerated(|&x| x == 0).rev()),
+            vec![(6, &[4][..]), (5, &[]), (2, &[2, 3]), (0, &[1])]
+        );
+        assert_eq!(
+            collect(v.splitn_enumerated(2, |&x| x == 0)),
+            vec![(0, &[1][..]), (2, &[2, 3, 0, 0, 4])]
+        );
+        assert_eq!(
+            collect(v.chunk_by_enumerated(|a, b| (*a == 0) == (*b == 0)).rev()),
+            vec![
+                (6, &[4][..]),
+                (4, &[0, 0]),
+                (2, &[2, 3]),
+                (1, &[0]),
+                (0, &[1])
+            ]
+        );
+    }
+}
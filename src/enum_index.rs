@@ -0,0 +1,504 @@
+//! Fieldless enums as index types together with fixed-size containers
+//! which do not require any heap allocations.
+use std::{
+    borrow::{Borrow, BorrowMut},
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{DoubleEndedIterator, FromIterator, FusedIterator},
+    ops::{Deref, DerefMut},
+    slice::{Iter, IterMut},
+};
+
+use crate::{
+    bitset::{Frame, FRAME_SIZE},
+    iter::IndexIter,
+    TIndex, TSlice,
+};
+
+/// An index type with a fixed number of values, usually implemented
+/// using `define_enum_index!`.
+pub trait EnumIndex: TIndex + 'static {
+    /// The number of distinct indices, all of them are smaller than `COUNT`.
+    const COUNT: usize;
+
+    /// All indices in ascending order.
+    const VARIANTS: &'static [Self];
+
+    /// `[T; Self::COUNT]`
+    type Array<T>: AsRef<[T]> + AsMut<[T]>;
+
+    /// The storage of a `TFixedBitSet<Self>`.
+    type Frames: Copy + AsRef<[u64]> + AsMut<[u64]>;
+
+    fn array_from_fn<T, F: FnMut(Self) -> T>(f: F) -> Self::Array<T>;
+
+    fn empty_frames() -> Self::Frames;
+
+    fn all() -> IndexIter<Self> {
+        IndexIter::new(Self::COUNT)
+    }
+}
+
+#[doc(hidden)]
+pub const fn frame_count(count: usize) -> usize {
+    count.div_ceil(FRAME_SIZE)
+}
+
+/// Declares fieldless enums which implement `EnumIndex`.
+///
+/// ```
+/// use tindex::{EnumIndex, TArray};
+///
+/// tindex::define_enum_index! {
+///     pub enum Phase {
+///         Parse,
+///         Lower,
+///         Codegen,
+///     }
+/// }
+///
+/// let mut timings: TArray<Phase, u32> = TArray::default();
+/// timings[Phase::Lower] += 3;
+/// assert_eq!(Phase::COUNT, 3);
+/// assert_eq!(Phase::all().last(), Some(Phase::Codegen));
+/// ```
+#[macro_export]
+macro_rules! define_enum_index {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_attr:meta])* $variant:ident),* $(,)?
+        }
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            $vis enum $name {
+                $($(#[$variant_attr])* $variant),*
+            }
+
+            impl $crate::TIndex for $name {
+                const MAX_INDEX: usize =
+                    <$name as $crate::EnumIndex>::COUNT.saturating_sub(1);
+
+                #[inline]
+                fn as_index(self) -> usize {
+                    self as usize
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    <$name as $crate::EnumIndex>::VARIANTS[index]
+                }
+
+                #[inline]
+                fn try_from_index(index: usize) -> Option<Self> {
+                    <$name as $crate::EnumIndex>::VARIANTS.get(index).copied()
+                }
+            }
+
+            impl $crate::EnumIndex for $name {
+                const COUNT: usize = <[&str]>::len(&[$(stringify!($variant)),*]);
+
+                const VARIANTS: &'static [Self] = &[$($name::$variant),*];
+
+                type Array<T> = [T; <$name as $crate::EnumIndex>::COUNT];
+
+                type Frames = [u64; $crate::enum_index::frame_count(
+                    <$name as $crate::EnumIndex>::COUNT,
+                )];
+
+                #[inline]
+                fn array_from_fn<T, F: FnMut(Self) -> T>(mut f: F) -> Self::Array<T> {
+                    ::std::array::from_fn(|i| f(<$name as $crate::TIndex>::from_index(i)))
+                }
+
+                #[inline]
+                fn empty_frames() -> Self::Frames {
+                    [0; $crate::enum_index::frame_count(<$name as $crate::EnumIndex>::COUNT)]
+                }
+            }
+        )*
+    };
+}
+
+/// A fixed-size array with one element for each value of `I`.
+pub struct TArray<I: EnumIndex, T> {
+    inner: I::Array<T>,
+}
+
+impl<I: EnumIndex, T> TArray<I, T> {
+    pub fn from_fn<F: FnMut(I) -> T>(f: F) -> Self {
+        Self {
+            inner: I::array_from_fn(f),
+        }
+    }
+
+    pub fn from_elem(elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(|_| elem.clone())
+    }
+
+    pub fn as_slice(&self) -> &TSlice<I, T> {
+        self.inner.as_ref().into()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut TSlice<I, T> {
+        self.inner.as_mut().into()
+    }
+}
+
+impl<I: EnumIndex, T: Default> Default for TArray<I, T> {
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<I: EnumIndex, T> Clone for TArray<I, T>
+where
+    I::Array<T>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I: EnumIndex, T> Copy for TArray<I, T> where I::Array<T>: Copy {}
+
+impl<I: EnumIndex + fmt::Debug, T: fmt::Debug> fmt::Debug for TArray<I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(I::VARIANTS.iter().zip(self.iter()))
+            .finish()
+    }
+}
+
+impl<I: EnumIndex, T: PartialEq> PartialEq for TArray<I, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<I: EnumIndex, T: Eq> Eq for TArray<I, T> {}
+
+impl<I: EnumIndex, T: Hash> Hash for TArray<I, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<I: EnumIndex, T> Deref for TArray<I, T> {
+    type Target = TSlice<I, T>;
+
+    fn deref(&self) -> &TSlice<I, T> {
+        self.as_slice()
+    }
+}
+
+impl<I: EnumIndex, T> DerefMut for TArray<I, T> {
+    fn deref_mut(&mut self) -> &mut TSlice<I, T> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: EnumIndex, T> Borrow<TSlice<I, T>> for TArray<I, T> {
+    fn borrow(&self) -> &TSlice<I, T> {
+        self
+    }
+}
+
+impl<I: EnumIndex, T> BorrowMut<TSlice<I, T>> for TArray<I, T> {
+    fn borrow_mut(&mut self) -> &mut TSlice<I, T> {
+        self
+    }
+}
+
+impl<I: EnumIndex, T> AsRef<TSlice<I, T>> for TArray<I, T> {
+    fn as_ref(&self) -> &TSlice<I, T> {
+        self
+    }
+}
+
+impl<I: EnumIndex, T> AsMut<TSlice<I, T>> for TArray<I, T> {
+    fn as_mut(&mut self) -> &mut TSlice<I, T> {
+        self
+    }
+}
+
+impl<'a, I: EnumIndex, T> IntoIterator for &'a TArray<I, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, I: EnumIndex, T> IntoIterator for &'a mut TArray<I, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// A bitset containing values of `I` without any heap allocations.
+pub struct TFixedBitSet<I: EnumIndex> {
+    inner: I::Frames,
+}
+
+impl<I: EnumIndex> Clone for TFixedBitSet<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: EnumIndex> Copy for TFixedBitSet<I> {}
+
+impl<I: EnumIndex> Default for TFixedBitSet<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: EnumIndex + fmt::Debug> fmt::Debug for TFixedBitSet<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<I: EnumIndex> PartialEq for TFixedBitSet<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.as_ref() == other.inner.as_ref()
+    }
+}
+
+impl<I: EnumIndex> Eq for TFixedBitSet<I> {}
+
+impl<I: EnumIndex> Hash for TFixedBitSet<I> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.as_ref().hash(state)
+    }
+}
+
+impl<I: EnumIndex> TFixedBitSet<I> {
+    pub fn new() -> Self {
+        Self {
+            inner: I::empty_frames(),
+        }
+    }
+
+    pub fn full() -> Self {
+        I::all().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.as_ref().iter().all(|&frame| frame == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.inner = I::empty_frames();
+    }
+
+    pub fn element_count(&self) -> usize {
+        self.inner
+            .as_ref()
+            .iter()
+            .map(|frame| frame.count_ones() as usize)
+            .sum()
+    }
+
+    #[inline]
+    fn frame_and_mask(idx: I) -> (usize, Frame) {
+        let idx = idx.as_index();
+        (idx / FRAME_SIZE, 1 << (idx % FRAME_SIZE))
+    }
+
+    pub fn set(&mut self, idx: I, value: bool) {
+        let (frame, mask) = Self::frame_and_mask(idx);
+        if value {
+            self.inner.as_mut()[frame] |= mask;
+        } else {
+            self.inner.as_mut()[frame] &= !mask;
+        }
+    }
+
+    pub fn add(&mut self, idx: I) {
+        self.set(idx, true)
+    }
+
+    pub fn remove(&mut self, idx: I) {
+        self.set(idx, false)
+    }
+
+    pub fn flip(&mut self, idx: I) {
+        let (frame, mask) = Self::frame_and_mask(idx);
+        self.inner.as_mut()[frame] ^= mask;
+    }
+
+    #[inline]
+    pub fn get(&self, idx: I) -> bool {
+        let (frame, mask) = Self::frame_and_mask(idx);
+        self.inner.as_ref()[frame] & mask != 0
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        self.inner
+            .as_ref()
+            .iter()
+            .zip(other.inner.as_ref())
+            .all(|(this, other)| (this | other) == *this)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.inner
+            .as_ref()
+            .iter()
+            .zip(other.inner.as_ref())
+            .any(|(l, r)| l & r != 0)
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.inner
+            .as_mut()
+            .iter_mut()
+            .zip(other.inner.as_ref())
+            .for_each(|(s, o)| *s &= o)
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.inner
+            .as_mut()
+            .iter_mut()
+            .zip(other.inner.as_ref())
+            .for_each(|(s, o)| *s |= o)
+    }
+
+    pub fn iter(&self) -> FixedIter<'_, I> {
+        FixedIter {
+            set: self,
+            indices: I::all(),
+        }
+    }
+
+    pub fn extend<U: IntoIterator<Item = I>>(&mut self, iter: U) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<I: EnumIndex> FromIterator<I> for TFixedBitSet<I> {
+    fn from_iter<U: IntoIterator<Item = I>>(iter: U) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, I: EnumIndex> IntoIterator for &'a TFixedBitSet<I> {
+    type Item = I;
+    type IntoIter = FixedIter<'a, I>;
+
+    fn into_iter(self) -> FixedIter<'a, I> {
+        self.iter()
+    }
+}
+
+pub struct FixedIter<'a, I: EnumIndex> {
+    set: &'a TFixedBitSet<I>,
+    indices: IndexIter<I>,
+}
+
+impl<I: EnumIndex> Clone for FixedIter<'_, I> {
+    fn clone(&self) -> Self {
+        Self {
+            set: self.set,
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<I: EnumIndex> Iterator for FixedIter<'_, I> {
+    type Item = I;
+
+    fn next(&mut self) -> Option<I> {
+        let set = self.set;
+        self.indices.find(|&idx| set.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.indices.len()))
+    }
+}
+
+impl<I: EnumIndex> DoubleEndedIterator for FixedIter<'_, I> {
+    fn next_back(&mut self) -> Option<I> {
+        let set = self.set;
+        self.indices.rfind(|&idx| set.get(idx))
+    }
+}
+
+impl<I: EnumIndex> FusedIterator for FixedIter<'_, I> {}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+
+    define_enum_index! {
+        enum Reg {
+            A,
+            B,
+            C,
+        }
+
+        enum Empty {}
+    }
+
+    #[test]
+    fn enum_index() {
+        assert_eq!(Reg::COUNT, 3);
+        assert_eq!(Reg::MAX_INDEX, 2);
+        assert_eq!(Reg::from_index(1), Reg::B);
+        assert_eq!(Reg::C.as_index(), 2);
+        assert_eq!(Reg::try_from_index(3), None);
+        assert_eq!(Reg::all().collect::<Vec<_>>(), vec![Reg::A, Reg::B, Reg::C]);
+        assert_eq!(Empty::COUNT, 0);
+        assert_eq!(Empty::all().next(), None);
+    }
+
+    #[test]
+    fn array() {
+        let mut arr = TArray::<Reg, u32>::from_fn(|r| r.as_index() as u32 * 10);
+        arr[Reg::B] += 1;
+        assert_eq!(arr.to_slice(), &[0, 11, 20]);
+        assert_eq!(arr.binary_search(&20), Ok(Reg::C));
+        assert_eq!(mem::size_of::<TArray<Reg, u32>>(), 12);
+        assert_eq!(format!("{:?}", arr), "{A: 0, B: 11, C: 20}");
+    }
+
+    #[test]
+    fn fixed_bitset() {
+        let mut set = TFixedBitSet::<Reg>::new();
+        assert!(set.is_empty());
+        set.add(Reg::C);
+        set.flip(Reg::A);
+        assert!(set.get(Reg::A));
+        assert!(!set.get(Reg::B));
+        assert_eq!(set.element_count(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Reg::A, Reg::C]);
+        assert_eq!(set.iter().rev().collect::<Vec<_>>(), vec![Reg::C, Reg::A]);
+        assert!(TFixedBitSet::full().contains(&set));
+        set.remove(Reg::A);
+        assert_eq!(set, [Reg::C].into_iter().collect());
+        assert_eq!(mem::size_of::<TFixedBitSet<Reg>>(), 8);
+        assert_eq!(mem::size_of::<TFixedBitSet<Empty>>(), 0);
+    }
+}
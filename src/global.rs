@@ -0,0 +1,479 @@
+//! Views into a part of a `TSlice` which keep using the indices of
+//! the whole slice.
+use std::{
+    fmt,
+    ops::{Bound, Index, IndexMut, RangeBounds},
+    slice::{Iter, IterMut},
+};
+
+use crate::{iter::Enumerated, slice_index, IndexRange, TIndex, TSlice};
+
+/// Resolves the global `range` to the local bounds `start..end` of a view
+/// starting at `offset` with length `len`, panicking if it is out of bounds.
+fn resolve_range<I: TIndex, R: RangeBounds<I>>(
+    range: &R,
+    offset: usize,
+    len: usize,
+) -> (usize, usize) {
+    let (start, end) = slice_index::bounds_as_usize(range);
+    let start = match start {
+        Bound::Included(start) => start,
+        Bound::Excluded(start) => start.checked_add(1).expect("range start overflow"),
+        Bound::Unbounded => offset,
+    };
+    let end = match end {
+        Bound::Included(end) => end.checked_add(1).expect("range end overflow"),
+        Bound::Excluded(end) => end,
+        Bound::Unbounded => offset + len,
+    };
+    assert!(
+        offset <= start && start <= end && end <= offset + len,
+        "range {}..{} out of bounds for view {}..{}",
+        start,
+        end,
+        offset,
+        offset + len
+    );
+    (start - offset, end - offset)
+}
+
+/// A shared view into the elements `start..end` of some `TSlice<I, T>`,
+/// indexed using the ids of the original slice.
+pub struct GlobalSlice<'a, I, T> {
+    offset: usize,
+    slice: &'a TSlice<I, T>,
+}
+
+/// A mutable view into the elements `start..end` of some `TSlice<I, T>`,
+/// indexed using the ids of the original slice.
+pub struct GlobalSliceMut<'a, I, T> {
+    offset: usize,
+    slice: &'a mut TSlice<I, T>,
+}
+
+impl<I, T> Clone for GlobalSlice<'_, I, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, T> Copy for GlobalSlice<'_, I, T> {}
+
+impl<I, T: fmt::Debug> fmt::Debug for GlobalSlice<'_, I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalSlice")
+            .field("offset", &self.offset)
+            .field("slice", &self.slice)
+            .finish()
+    }
+}
+
+impl<I, T: fmt::Debug> fmt::Debug for GlobalSliceMut<'_, I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalSliceMut")
+            .field("offset", &self.offset)
+            .field("slice", &self.slice)
+            .finish()
+    }
+}
+
+impl<'a, I: TIndex, T> GlobalSlice<'a, I, T> {
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// The id of the first element of this view.
+    pub fn start(&self) -> I {
+        I::from_index(self.offset)
+    }
+
+    /// The ids of all elements of this view.
+    pub fn index_range(&self) -> IndexRange<I> {
+        IndexRange::from_usize(self.offset, self.offset + self.slice.len())
+    }
+
+    pub fn contains_id(&self, id: I) -> bool {
+        self.local(id).is_some()
+    }
+
+    fn local(&self, id: I) -> Option<usize> {
+        id.as_index()
+            .checked_sub(self.offset)
+            .filter(|&idx| idx < self.slice.len())
+    }
+
+    pub fn get(&self, id: I) -> Option<&'a T> {
+        self.local(id).map(|idx| &self.slice.inner[idx])
+    }
+
+    /// Returns the elements of this view as a `TSlice` starting at index zero.
+    pub fn as_local(&self) -> &'a TSlice<I, T> {
+        self.slice
+    }
+
+    pub fn iter(&self) -> Iter<'a, T> {
+        self.slice.inner.iter()
+    }
+
+    pub fn iter_enumerated(&self) -> Enumerated<I, Iter<'a, T>> {
+        Enumerated::with_offset(self.slice.inner.iter(), self.offset)
+    }
+
+    /// Returns the elements of `range`, which has to be a subrange of this view.
+    pub fn slice_global<R: RangeBounds<I>>(&self, range: R) -> Self {
+        let (start, end) = self.resolve_global(&range);
+        GlobalSlice {
+            offset: self.offset + start,
+            slice: self.slice.inner[start..end].into(),
+        }
+    }
+
+    fn resolve_global<R: RangeBounds<I>>(&self, range: &R) -> (usize, usize) {
+        resolve_range(range, self.offset, self.slice.len())
+    }
+
+    /// Splits this view at the global id `mid`.
+    pub fn split_at_global(&self, mid: I) -> (Self, Self) {
+        let mid_local = mid
+            .as_index()
+            .checked_sub(self.offset)
+            .expect("`mid` before the start of the view");
+        let (left, right) = self.slice.inner.split_at(mid_local);
+        (
+            GlobalSlice {
+                offset: self.offset,
+                slice: left.into(),
+            },
+            GlobalSlice {
+                offset: self.offset + mid_local,
+                slice: right.into(),
+            },
+        )
+    }
+
+    /// Returns the first element and the remaining ones, like `TSlice::split_first`.
+    pub fn split_first(&self) -> Option<(&'a T, Self)> {
+        let (_, first, rest) = self.split_first_id()?;
+        Some((first, rest))
+    }
+
+    pub fn split_last(&self) -> Option<(&'a T, Self)> {
+        let (_, last, rest) = self.split_last_id()?;
+        Some((last, rest))
+    }
+
+    /// Like `split_first`, but also returns the id of the first element.
+    pub fn split_first_id(&self) -> Option<(I, &'a T, Self)> {
+        let (first, rest) = self.slice.inner.split_first()?;
+        let rest = GlobalSlice {
+            offset: self.offset + 1,
+            slice: rest.into(),
+        };
+        Some((I::from_index(self.offset), first, rest))
+    }
+
+    pub fn split_last_id(&self) -> Option<(I, &'a T, Self)> {
+        let (last, rest) = self.slice.inner.split_last()?;
+        let id = I::from_index(self.offset + rest.len());
+        let rest = GlobalSlice {
+            offset: self.offset,
+            slice: rest.into(),
+        };
+        Some((id, last, rest))
+    }
+}
+
+impl<'a, I: TIndex, T> GlobalSliceMut<'a, I, T> {
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    pub fn as_global(&self) -> GlobalSlice<'_, I, T> {
+        GlobalSlice {
+            offset: self.offset,
+            slice: self.slice,
+        }
+    }
+
+    pub fn reborrow(&mut self) -> GlobalSliceMut<'_, I, T> {
+        GlobalSliceMut {
+            offset: self.offset,
+            slice: self.slice,
+        }
+    }
+
+    pub fn start(&self) -> I {
+        self.as_global().start()
+    }
+
+    pub fn index_range(&self) -> IndexRange<I> {
+        self.as_global().index_range()
+    }
+
+    pub fn contains_id(&self, id: I) -> bool {
+        self.as_global().contains_id(id)
+    }
+
+    pub fn get(&self, id: I) -> Option<&T> {
+        self.as_global().get(id)
+    }
+
+    pub fn get_mut(&mut self, id: I) -> Option<&mut T> {
+        let idx = self.as_global().local(id)?;
+        Some(&mut self.slice.inner[idx])
+    }
+
+    pub fn as_local(&mut self) -> &mut TSlice<I, T> {
+        self.slice
+    }
+
+    pub fn into_local(self) -> &'a mut TSlice<I, T> {
+        self.slice
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.slice.inner.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.slice.inner.iter_mut()
+    }
+
+    pub fn iter_enumerated(&self) -> Enumerated<I, Iter<'_, T>> {
+        self.as_global().iter_enumerated()
+    }
+
+    pub fn iter_enumerated_mut(&mut self) -> Enumerated<I, IterMut<'_, T>> {
+        Enumerated::with_offset(self.slice.inner.iter_mut(), self.offset)
+    }
+
+    /// Returns the elements of `range`, which has to be a subrange of this view.
+    pub fn slice_global_mut<R: RangeBounds<I>>(self, range: R) -> Self {
+        let (start, end) = self.as_global().resolve_global(&range);
+        GlobalSliceMut {
+            offset: self.offset + start,
+            slice: (&mut self.slice.inner[start..end]).into(),
+        }
+    }
+
+    /// Splits this view at the global id `mid`.
+    pub fn split_at_global_mut(self, mid: I) -> (Self, Self) {
+        let mid_local = mid
+            .as_index()
+            .checked_sub(self.offset)
+            .expect("`mid` before the start of the view");
+        let (left, right) = self.slice.inner.split_at_mut(mid_local);
+        (
+            GlobalSliceMut {
+                offset: self.offset,
+                slice: left.into(),
+            },
+            GlobalSliceMut {
+                offset: self.offset + mid_local,
+                slice: right.into(),
+            },
+        )
+    }
+
+    pub fn split_first_mut(self) -> Option<(&'a mut T, Self)> {
+        let (_, first, rest) = self.split_first_id_mut()?;
+        Some((first, rest))
+    }
+
+    pub fn split_last_mut(self) -> Option<(&'a mut T, Self)> {
+        let (_, last, rest) = self.split_last_id_mut()?;
+        Some((last, rest))
+    }
+
+    pub fn split_first_id_mut(self) -> Option<(I, &'a mut T, Self)> {
+        let (first, rest) = self.slice.inner.split_first_mut()?;
+        let rest = GlobalSliceMut {
+            offset: self.offset + 1,
+            slice: rest.into(),
+        };
+        Some((I::from_index(self.offset), first, rest))
+    }
+
+    pub fn split_last_id_mut(self) -> Option<(I, &'a mut T, Self)> {
+        let (last, rest) = self.slice.inner.split_last_mut()?;
+        let id = I::from_index(self.offset + rest.len());
+        let rest = GlobalSliceMut {
+            offset: self.offset,
+            slice: rest.into(),
+        };
+        Some((id, last, rest))
+    }
+}
+
+impl<I: TIndex, T> Index<I> for GlobalSlice<'_, I, T> {
+    type Output = T;
+
+    fn index(&self, id: I) -> &T {
+        match self.local(id) {
+            Some(idx) => &self.slice.inner[idx],
+            None => panic!(
+                "index {} out of range for view {}..{}",
+                id.as_index(),
+                self.offset,
+                self.offset + self.slice.len()
+            ),
+        }
+    }
+}
+
+impl<I: TIndex, T> Index<I> for GlobalSliceMut<'_, I, T> {
+    type Output = T;
+
+    fn index(&self, id: I) -> &T {
+        let idx = self.as_global().local(id);
+        match idx {
+            Some(idx) => &self.slice.inner[idx],
+            None => panic!(
+                "index {} out of range for view {}..{}",
+                id.as_index(),
+                self.offset,
+                self.offset + self.slice.len()
+            ),
+        }
+    }
+}
+
+impl<I: TIndex, T> IndexMut<I> for GlobalSliceMut<'_, I, T> {
+    fn index_mut(&mut self, id: I) -> &mut T {
+        match self.as_global().local(id) {
+            Some(idx) => &mut self.slice.inner[idx],
+            None => panic!(
+                "index {} out of range for view {}..{}",
+                id.as_index(),
+                self.offset,
+                self.offset + self.slice.len()
+            ),
+        }
+    }
+}
+
+impl<I: TIndex, T> TSlice<I, T> {
+    /// Returns a view of the whole slice which can then be further split
+    /// while keeping the ids of `self`.
+    pub fn as_global(&self) -> GlobalSlice<'_, I, T> {
+        GlobalSlice {
+            offset: 0,
+            slice: self,
+        }
+    }
+
+    pub fn as_global_mut(&mut self) -> GlobalSliceMut<'_, I, T> {
+        GlobalSliceMut {
+            offset: 0,
+            slice: self,
+        }
+    }
+
+    /// Returns a view of the elements in `range` which is indexed using the
+    /// ids of `self`, unlike `&self[range]` which starts at index zero.
+    pub fn slice_global<R: RangeBounds<I>>(&self, range: R) -> GlobalSlice<'_, I, T> {
+        self.as_global().slice_global(range)
+    }
+
+    pub fn slice_global_mut<R: RangeBounds<I>>(&mut self, range: R) -> GlobalSliceMut<'_, I, T> {
+        self.as_global_mut().slice_global_mut(range)
+    }
+
+    pub fn split_at_global(&self, mid: I) -> (GlobalSlice<'_, I, T>, GlobalSlice<'_, I, T>) {
+        self.as_global().split_at_global(mid)
+    }
+
+    pub fn split_at_global_mut(
+        &mut self,
+        mid: I,
+    ) -> (GlobalSliceMut<'_, I, T>, GlobalSliceMut<'_, I, T>) {
+        self.as_global_mut().split_at_global_mut(mid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use crate::{tvec, TVec};
+
+    #[test]
+    fn global_slice() {
+        let v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd', 'e', 'f'];
+        let sub = v.slice_global(2..5);
+        assert_eq!(sub.len(), 3);
+        assert_eq!(sub[3], 'd');
+        assert_eq!(sub.get(1), None);
+        assert_eq!(sub.get(5), None);
+        assert!(sub.contains_id(4));
+        assert_eq!(
+            sub.iter_enumerated().rev().collect::<Vec<_>>(),
+            vec![(4, &'e'), (3, &'d'), (2, &'c')]
+        );
+
+        let (left, right) = sub.split_at_global(4);
+        assert_eq!(left.index_range().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(right.start(), 4);
+        assert_eq!(right[4], 'e');
+        assert_eq!(sub.slice_global(3..=3).as_local().to_slice(), &['d']);
+
+        let (id, first, rest) = sub.split_first_id().unwrap();
+        assert_eq!((id, first), (2, &'c'));
+        assert_eq!(rest.split_first_id().map(|(id, _, _)| id), Some(3));
+        assert_eq!(rest.split_last_id().map(|(id, _, _)| id), Some(4));
+        let (last, rest) = rest.split_last().unwrap();
+        assert_eq!((last, rest.start()), (&'e', 3));
+    }
+
+    #[test]
+    fn global_slice_mut() {
+        let mut v: TVec<u32, u32> = tvec![0, 1, 2, 3, 4, 5];
+        let (mut left, mut right) = v.split_at_global_mut(3);
+        right[4] += left[1];
+        left[2] = right[5];
+        assert_eq!(right.get_mut(2), None);
+        for (id, x) in right.iter_enumerated_mut() {
+            *x += id * 10;
+        }
+        let mut sub = v.slice_global_mut(1..);
+        let mut rest = sub.reborrow();
+        while let Some((id, x, tail)) = rest.split_first_id_mut() {
+            *x += id * 100;
+            rest = tail;
+        }
+        assert_eq!(sub[1], 101);
+        assert_eq!(v, tvec![0, 101, 205, 333, 445, 555]);
+    }
+
+    #[test]
+    fn excluded_start() {
+        let v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd'];
+        let sub = v.slice_global(2..);
+        let all = sub.slice_global((Bound::Excluded(1), Bound::Unbounded));
+        assert_eq!(all.index_range(), sub.index_range());
+        let tail = sub.slice_global((Bound::Excluded(2), Bound::Included(3)));
+        assert_eq!(tail.as_local().to_slice(), &['d']);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for view 2..4")]
+    fn range_before_view() {
+        let v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd'];
+        v.slice_global(2..).slice_global(1..);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_outside_view() {
+        let v: TVec<u32, char> = tvec!['a', 'b', 'c'];
+        let _ = v.slice_global(1..)[0];
+    }
+}
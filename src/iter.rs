@@ -1,10 +1,10 @@
 use std::{
-    iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator},
+    iter::{DoubleEndedIterator, Enumerate, ExactSizeIterator, FusedIterator},
     marker::PhantomData,
     ops::Range,
 };
 
-use crate::TIndex;
+use crate::{IndexRange, TIndex};
 
 #[derive(Debug)]
 pub struct IndexIter<I> {
@@ -14,11 +14,46 @@ pub struct IndexIter<I> {
 
 impl<I> IndexIter<I> {
     pub(crate) fn new(end: usize) -> Self {
+        Self::from_usize(0, end)
+    }
+
+    pub(crate) fn from_usize(start: usize, end: usize) -> Self {
         Self {
             _marker: PhantomData,
-            inner: 0..end,
+            inner: start..end,
         }
     }
+
+    /// Reinterprets `self` as an iterator over `J`, see [`TVec::cast_index`](crate::TVec::cast_index).
+    pub fn cast_index<J>(self) -> IndexIter<J> {
+        IndexIter::from_usize(self.inner.start, self.inner.end)
+    }
+}
+
+impl<I> From<IndexRange<I>> for IndexIter<I> {
+    fn from(range: IndexRange<I>) -> Self {
+        let range = range.as_usize();
+        Self::from_usize(range.start, range.end)
+    }
+}
+
+impl<I> From<IndexIter<I>> for IndexRange<I> {
+    fn from(iter: IndexIter<I>) -> Self {
+        IndexRange::from_usize(iter.inner.start, iter.inner.end)
+    }
+}
+
+impl<I: TIndex> IndexIter<I> {
+    /// Returns an iterator over all indices in `start..end`.
+    pub fn between(start: I, end: I) -> Self {
+        Self::from_usize(start.as_index(), end.as_index())
+    }
+
+    /// Returns an iterator over all indices in `start..=end`.
+    pub fn between_inclusive(start: I, end: I) -> Self {
+        let end = end.as_index().checked_add(1).expect("index overflow");
+        Self::from_usize(start.as_index(), end)
+    }
 }
 
 impl<I> Clone for IndexIter<I> {
@@ -30,22 +65,48 @@ impl<I> Clone for IndexIter<I> {
     }
 }
 
+#[inline]
+fn from_index<I: TIndex>(index: usize) -> I {
+    debug_assert!(index <= I::MAX_INDEX, "index overflow in `IndexIter`");
+    I::from_index(index)
+}
+
 impl<I: TIndex> Iterator for IndexIter<I> {
     type Item = I;
 
     fn next(&mut self) -> Option<I> {
-        self.inner.next().map(I::from_index)
+        self.inner.next().map(from_index)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.inner.size_hint()
     }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<I> {
+        self.inner.nth(n).map(from_index)
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<I> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.len()
+    }
 }
 
 impl<I: TIndex> DoubleEndedIterator for IndexIter<I> {
     #[inline]
     fn next_back(&mut self) -> Option<I> {
-        self.inner.next_back().map(I::from_index)
+        self.inner.next_back().map(from_index)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<I> {
+        self.inner.nth_back(n).map(from_index)
     }
 }
 
@@ -56,3 +117,122 @@ impl<I: TIndex> ExactSizeIterator for IndexIter<I> {
 }
 
 impl<I: TIndex> FusedIterator for IndexIter<I> {}
+
+/// An iterator yielding each element together with its typed index.
+#[derive(Debug)]
+pub struct Enumerated<I, It> {
+    _marker: PhantomData<fn(I)>,
+    inner: Enumerate<It>,
+    offset: usize,
+}
+
+impl<I, It: Iterator> Enumerated<I, It> {
+    pub(crate) fn new(iter: It) -> Self {
+        Self::with_offset(iter, 0)
+    }
+
+    /// Yields `offset` as the index of the first element.
+    pub(crate) fn with_offset(iter: It, offset: usize) -> Self {
+        Self {
+            _marker: PhantomData,
+            inner: iter.enumerate(),
+            offset,
+        }
+    }
+}
+
+impl<I, It: Clone> Clone for Enumerated<I, It> {
+    fn clone(&self) -> Self {
+        Self {
+            _marker: PhantomData,
+            inner: self.inner.clone(),
+            offset: self.offset,
+        }
+    }
+}
+
+impl<I: TIndex, It: Iterator> Iterator for Enumerated<I, It> {
+    type Item = (I, It::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(i, item)| (from_index(self.offset + i), item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner
+            .nth(n)
+            .map(|(i, item)| (from_index(self.offset + i), item))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+}
+
+impl<I: TIndex, It> DoubleEndedIterator for Enumerated<I, It>
+where
+    It: DoubleEndedIterator + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(i, item)| (from_index(self.offset + i), item))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner
+            .nth_back(n)
+            .map(|(i, item)| (from_index(self.offset + i), item))
+    }
+}
+
+impl<I: TIndex, It: ExactSizeIterator> ExactSizeIterator for Enumerated<I, It> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<I: TIndex, It: FusedIterator> FusedIterator for Enumerated<I, It> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_iter() {
+        let iter = IndexIter::between(3u32, 1_000_000_000);
+        assert_eq!(iter.len(), 999_999_997);
+        assert_eq!(iter.clone().nth(1000), Some(1003));
+        assert_eq!(
+            iter.clone().skip(10).take(2).collect::<Vec<_>>(),
+            vec![13, 14]
+        );
+        assert_eq!(iter.clone().rev().nth(1), Some(999_999_998));
+        assert_eq!(iter.clone().last(), Some(999_999_999));
+        assert_eq!(iter.clone().count(), 999_999_997);
+        assert_eq!(
+            iter.step_by(400_000_000).collect::<Vec<_>>(),
+            vec![3, 400_000_003, 800_000_003]
+        );
+
+        let range = IndexRange::from(IndexIter::between(2u32, 5).cast_index::<usize>());
+        assert_eq!(range, IndexRange::new(2, 5));
+        assert_eq!(IndexIter::from(range).last(), Some(4));
+
+        let mut iter = IndexIter::between_inclusive(2u8, 255);
+        assert_eq!(iter.nth_back(253), Some(2));
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -1,54 +1,251 @@
 use std::{
+    alloc::Layout,
     borrow::{Borrow, BorrowMut, ToOwned},
     cmp::{Eq, Ordering, PartialEq},
+    error::Error,
     fmt,
     hash::{Hash, Hasher},
     iter::FromIterator,
     marker::PhantomData,
-    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+    mem::ManuallyDrop,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+    ops::{Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr,
     slice::{Iter, IterMut, Windows},
-    vec::{IntoIter, Splice},
+    vec::{Drain, ExtractIf, IntoIter, Splice},
 };
 
 pub mod bitset;
+pub mod chunks;
+pub mod enum_index;
+pub mod global;
 pub mod iter;
+pub mod niche;
+pub mod permutation;
+pub mod range;
+pub mod remap;
 pub mod slice_index;
 
 pub use bitset::TBitSet;
-
-use iter::IndexIter;
+pub use enum_index::{EnumIndex, TArray, TFixedBitSet};
+pub use global::{GlobalSlice, GlobalSliceMut};
+pub use niche::{NonMaxU16, NonMaxU32, NonMaxU64, NonMaxU8, NonMaxUsize};
+pub use permutation::Permutation;
+pub use range::IndexRange;
+pub use remap::IndexRemap;
+
+use iter::{Enumerated, IndexIter};
 use slice_index::TSliceIndex;
 
 pub trait TIndex: PartialEq + Eq + Clone + Copy {
+    /// The largest index representable by this type.
+    const MAX_INDEX: usize = usize::MAX;
+
     fn as_index(self) -> usize;
 
     fn from_index(index: usize) -> Self;
-}
 
-impl TIndex for usize {
+    /// Converts `index` to `Self`, returning `None` if it exceeds `MAX_INDEX`.
     #[inline]
-    fn as_index(self) -> usize {
-        self
+    fn try_from_index(index: usize) -> Option<Self> {
+        if index <= Self::MAX_INDEX {
+            Some(Self::from_index(index))
+        } else {
+            None
+        }
     }
 
+    // Note that primitive integers have inherent methods with the same names,
+    // so these have to be called as `TIndex::checked_add(i, 1)` for them.
+
     #[inline]
-    fn from_index(index: usize) -> Self {
-        index
+    fn checked_add(self, rhs: usize) -> Option<Self> {
+        self.as_index()
+            .checked_add(rhs)
+            .and_then(Self::try_from_index)
     }
-}
 
-impl TIndex for u32 {
     #[inline]
-    fn as_index(self) -> usize {
-        self as usize
+    fn checked_sub(self, rhs: usize) -> Option<Self> {
+        self.as_index()
+            .checked_sub(rhs)
+            .and_then(Self::try_from_index)
     }
 
+    /// Returns the index directly after `self`, if it exists.
     #[inline]
-    fn from_index(index: usize) -> Self {
-        index as u32
+    fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    /// Returns the index directly before `self`, if it exists.
+    #[inline]
+    fn prev(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    #[inline]
+    fn offset(self, offset: isize) -> Option<Self> {
+        if offset < 0 {
+            self.checked_sub(offset.unsigned_abs())
+        } else {
+            self.checked_add(offset as usize)
+        }
+    }
+
+    /// Returns the signed distance from `self` to `other`, so that
+    /// `self.offset(self.distance_to(other)) == Some(other)`.
+    ///
+    /// Panics if the distance does not fit into an `isize`.
+    #[inline]
+    fn distance_to(self, other: Self) -> isize {
+        let (from, to) = (self.as_index(), other.as_index());
+        let distance = if from <= to {
+            isize::try_from(to - from)
+        } else {
+            isize::try_from(from - to).map(|d| -d)
+        };
+        distance.expect("index distance overflowed `isize`")
+    }
+}
+
+/// Returns `max` as an index, saturating at `usize::MAX`.
+pub(crate) const fn max_index(max: u128) -> usize {
+    if max > usize::MAX as u128 {
+        usize::MAX
+    } else {
+        max as usize
+    }
+}
+
+macro_rules! unsigned_index {
+    ($($int:ident),*) => {
+        $(
+            impl TIndex for $int {
+                const MAX_INDEX: usize = max_index($int::MAX as u128);
+
+                #[inline]
+                fn as_index(self) -> usize {
+                    usize::try_from(self).unwrap_or_else(|_| {
+                        panic!("`{}` out of range for `usize`", self)
+                    })
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    Self::try_from_index(index).unwrap_or_else(|| {
+                        panic!("index {} out of range for `{}`", index, stringify!($int))
+                    })
+                }
+
+                #[inline]
+                fn try_from_index(index: usize) -> Option<Self> {
+                    $int::try_from(index).ok()
+                }
+            }
+        )*
+    };
+}
+
+unsigned_index!(u8, u16, u32, u64, usize);
+
+// `NonZero*` indices use a 1-based encoding, so `NonZeroU32::new(1)`
+// is the first index.
+macro_rules! non_zero_index {
+    ($($non_zero:ident($int:ident)),*) => {
+        $(
+            impl TIndex for $non_zero {
+                const MAX_INDEX: usize = max_index($int::MAX as u128 - 1);
+
+                #[inline]
+                fn as_index(self) -> usize {
+                    (self.get() - 1).as_index()
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    Self::try_from_index(index).unwrap_or_else(|| {
+                        panic!("index {} out of range for `{}`", index, stringify!($non_zero))
+                    })
+                }
+
+                #[inline]
+                fn try_from_index(index: usize) -> Option<Self> {
+                    $int::try_from_index(index)?
+                        .checked_add(1)
+                        .and_then($non_zero::new)
+                }
+            }
+        )*
+    };
+}
+
+non_zero_index!(
+    NonZeroU8(u8),
+    NonZeroU16(u16),
+    NonZeroU32(u32),
+    NonZeroU64(u64),
+    NonZeroUsize(usize)
+);
+
+/// The error returned when an index does not fit into the index type `I`.
+pub struct IndexOverflow<I> {
+    _marker: PhantomData<fn(I)>,
+    index: usize,
+}
+
+impl<I> IndexOverflow<I> {
+    pub(crate) fn new(index: usize) -> Self {
+        Self {
+            _marker: PhantomData,
+            index,
+        }
+    }
+
+    /// The index which could not be represented.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<I> Clone for IndexOverflow<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for IndexOverflow<I> {}
+
+impl<I> PartialEq for IndexOverflow<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
     }
 }
 
+impl<I> Eq for IndexOverflow<I> {}
+
+impl<I> fmt::Debug for IndexOverflow<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexOverflow")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<I: TIndex> fmt::Display for IndexOverflow<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} exceeds the maximum index {} of `{}`",
+            self.index,
+            I::MAX_INDEX,
+            std::any::type_name::<I>()
+        )
+    }
+}
+
+impl<I: TIndex> Error for IndexOverflow<I> {}
+
 #[repr(transparent)]
 pub struct TSlice<I, T> {
     _marker: PhantomData<fn(I)>,
@@ -112,6 +309,12 @@ impl<I, T> TSlice<I, T> {
         self.inner.split_last().map(|(t, slice)| (t, slice.into()))
     }
 
+    pub fn split_last_mut(&mut self) -> Option<(&mut T, &mut Self)> {
+        self.inner
+            .split_last_mut()
+            .map(|(t, slice)| (t, slice.into()))
+    }
+
     pub fn contains(&self, item: &T) -> bool
     where
         T: PartialEq<T>,
@@ -127,10 +330,27 @@ impl<I, T> TSlice<I, T> {
         self.inner.iter_mut()
     }
 
-    pub fn to_slice<'a>(&'a self) -> &'a [T] {
+    pub fn to_slice(&self) -> &[T] {
         &self.inner
     }
 
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.inner
+    }
+
+    /// Reinterprets `self` as a slice indexed by `J`, see [`TVec::cast_index`].
+    ///
+    /// This is not called `cast_index`, as the by-value `TVec::cast_index`
+    /// would take precedence when called on a `&TVec`.
+    pub fn cast_index_ref<J>(&self) -> &TSlice<J, T> {
+        self.inner.as_ref().into()
+    }
+
+    /// Mutable version of [`TSlice::cast_index_ref`].
+    pub fn cast_index_mut<J>(&mut self) -> &mut TSlice<J, T> {
+        self.inner.as_mut().into()
+    }
+
     pub fn sort(&mut self)
     where
         T: Ord,
@@ -161,18 +381,189 @@ impl<I, T> TSlice<I, T> {
         self.inner.sort_by_cached_key(f)
     }
 
-    pub fn windows(&self, size: usize) -> Windows<T> {
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
         self.inner.windows(size)
     }
+
+    /// Returns a new `TVec` with the same indices as `self`.
+    pub fn map<U, F>(&self, f: F) -> TVec<I, U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.inner.iter().map(f).collect()
+    }
+
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.inner.sort_unstable()
+    }
+
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.inner.sort_unstable_by(compare)
+    }
+
+    pub fn sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.inner.sort_unstable_by_key(f)
+    }
+
+    pub fn reverse(&mut self) {
+        self.inner.reverse()
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.inner.fill(value)
+    }
+
+    pub fn fill_with<F>(&mut self, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.inner.fill_with(f)
+    }
+
+    pub fn copy_from_slice(&mut self, src: &Self)
+    where
+        T: Copy,
+    {
+        self.inner.copy_from_slice(&src.inner)
+    }
+
+    pub fn clone_from_slice(&mut self, src: &Self)
+    where
+        T: Clone,
+    {
+        self.inner.clone_from_slice(&src.inner)
+    }
+
+    pub fn swap_with_slice(&mut self, other: &mut Self) {
+        self.inner.swap_with_slice(&mut other.inner)
+    }
+
+    pub fn starts_with(&self, needle: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.inner.starts_with(&needle.inner)
+    }
+
+    pub fn ends_with(&self, needle: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.inner.ends_with(&needle.inner)
+    }
 }
 
 impl<I: TIndex, T> TSlice<I, T> {
-    pub fn get(&self, idx: I) -> Option<&T> {
-        self.inner.get(idx.as_index())
+    /// Returns the element or subslice at `idx`, or `None` if it is out of bounds.
+    pub fn get<S: TSliceIndex<Self>>(&self, idx: S) -> Option<&S::Output> {
+        idx.get(self)
+    }
+
+    pub fn get_mut<S: TSliceIndex<Self>>(&mut self, idx: S) -> Option<&mut S::Output> {
+        idx.get_mut(self)
+    }
+
+    /// Returns a reference to the element or subslice at `idx` without
+    /// bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `idx` has to be in bounds. This is only checked if debug assertions
+    /// are enabled.
+    pub unsafe fn get_unchecked<S: TSliceIndex<Self>>(&self, idx: S) -> &S::Output {
+        // SAFETY: the caller guarantees that `idx` is in bounds
+        unsafe { idx.get_unchecked(self) }
+    }
+
+    /// Returns a mutable reference to the element or subslice at `idx`
+    /// without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `idx` has to be in bounds. This is only checked if debug assertions
+    /// are enabled.
+    pub unsafe fn get_unchecked_mut<S: TSliceIndex<Self>>(&mut self, idx: S) -> &mut S::Output {
+        // SAFETY: the caller guarantees that `idx` is in bounds
+        unsafe { idx.get_unchecked_mut(self) }
+    }
+
+    /// Returns mutable references to the elements at all `indices` at once.
+    ///
+    /// Returns `None` if any index is out of bounds or used more than once.
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [I; N]) -> Option<[&mut T; N]> {
+        self.inner.get_disjoint_mut(indices.map(I::as_index)).ok()
+    }
+
+    /// Returns mutable references to the elements at `a` and `b`.
+    ///
+    /// Panics if `a == b` or if either index is out of bounds.
+    pub fn pick2_mut(&mut self, a: I, b: I) -> (&mut T, &mut T) {
+        let (a, b) = (a.as_index(), b.as_index());
+        assert_ne!(a, b, "`pick2_mut` called with the same index twice");
+        let [a, b] = self
+            .inner
+            .get_disjoint_mut([a, b])
+            .expect("index out of bounds");
+        (a, b)
+    }
+
+    /// Returns mutable subslices for all `ranges` at once.
+    ///
+    /// Returns `None` if any range is out of bounds or if two ranges overlap.
+    pub fn get_many_ranges_mut<const N: usize>(
+        &mut self,
+        ranges: [Range<I>; N],
+    ) -> Option<[&mut Self; N]> {
+        let ranges = ranges.map(|r| r.start.as_index()..r.end.as_index());
+        let slices = self.inner.get_disjoint_mut(ranges).ok()?;
+        Some(slices.map(Into::into))
+    }
+
+    pub fn map_enumerated<U, F>(&self, mut f: F) -> TVec<I, U>
+    where
+        F: FnMut(I, &T) -> U,
+    {
+        self.iter_enumerated().map(|(i, elem)| f(i, elem)).collect()
     }
 
-    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
-        self.inner.get_mut(idx.as_index())
+    /// Like `map`, but stops at the first error, returning it together
+    /// with the index of the element for which it occurred.
+    pub fn try_map<U, E, F>(&self, mut f: F) -> Result<TVec<I, U>, (I, E)>
+    where
+        F: FnMut(&T) -> Result<U, E>,
+    {
+        self.iter_enumerated()
+            .map(|(i, elem)| f(elem).map_err(|e| (i, e)))
+            .collect()
+    }
+
+    pub fn iter_enumerated(&self) -> Enumerated<I, Iter<'_, T>> {
+        Enumerated::new(self.inner.iter())
+    }
+
+    pub fn iter_enumerated_mut(&mut self) -> Enumerated<I, IterMut<'_, T>> {
+        Enumerated::new(self.inner.iter_mut())
+    }
+
+    pub fn first_id(&self) -> Option<I> {
+        if self.inner.is_empty() {
+            None
+        } else {
+            Some(I::from_index(0))
+        }
     }
 
     pub fn last_id(&self) -> Option<I> {
@@ -191,10 +582,58 @@ impl<I: TIndex, T> TSlice<I, T> {
         I::from_index(self.inner.len())
     }
 
+    /// Returns the first element and a view of the remaining ones,
+    /// which keep the ids they have in `self`.
+    pub fn split_first(&self) -> Option<(&T, GlobalSlice<'_, I, T>)> {
+        let (_, first, rest) = self.split_first_id()?;
+        Some((first, rest))
+    }
+
+    pub fn split_first_mut(&mut self) -> Option<(&mut T, GlobalSliceMut<'_, I, T>)> {
+        let (_, first, rest) = self.split_first_id_mut()?;
+        Some((first, rest))
+    }
+
+    /// Like `split_first`, but also returns the id of the first element.
+    ///
+    /// Use `GlobalSlice::split_first_id` to continue splitting the tail.
+    pub fn split_first_id(&self) -> Option<(I, &T, GlobalSlice<'_, I, T>)> {
+        self.as_global().split_first_id()
+    }
+
+    pub fn split_first_id_mut(&mut self) -> Option<(I, &mut T, GlobalSliceMut<'_, I, T>)> {
+        self.as_global_mut().split_first_id_mut()
+    }
+
+    /// Like `split_last`, but also returns the id of the last element.
+    ///
+    /// As the remaining elements are a prefix of `self`, their ids
+    /// are unchanged.
+    pub fn split_last_id(&self) -> Option<(I, &T, &Self)> {
+        let id = self.last_id()?;
+        let (last, rest) = self.split_last()?;
+        Some((id, last, rest))
+    }
+
+    pub fn split_last_id_mut(&mut self) -> Option<(I, &mut T, &mut Self)> {
+        let id = self.last_id()?;
+        let (last, rest) = self.split_last_mut()?;
+        Some((id, last, rest))
+    }
+
     pub fn index_iter(&self) -> IndexIter<I> {
         IndexIter::new(self.inner.len())
     }
 
+    /// Returns an iterator over all indices starting at `start`.
+    pub fn index_iter_from(&self, start: I) -> IndexIter<I> {
+        IndexIter::from_usize(start.as_index(), self.inner.len())
+    }
+
+    pub fn index_range(&self) -> IndexRange<I> {
+        IndexRange::from_usize(0, self.inner.len())
+    }
+
     pub fn swap(&mut self, a: I, b: I) {
         self.inner.swap(a.as_index(), b.as_index())
     }
@@ -209,6 +648,68 @@ impl<I: TIndex, T> TSlice<I, T> {
         (left.into(), right.into())
     }
 
+    /// Rotates the slice so that the element at `mid` becomes the first.
+    pub fn rotate_left(&mut self, mid: I) {
+        self.inner.rotate_left(mid.as_index())
+    }
+
+    /// Rotates the slice so that the last `k` elements become the first,
+    /// moving the element at `I::from_index(0)` to `k`.
+    pub fn rotate_right(&mut self, k: I) {
+        self.inner.rotate_right(k.as_index())
+    }
+
+    /// Splits `self` around the element at `index`, keeping the ids of `self`.
+    fn split_at_pivot_mut(
+        &mut self,
+        index: I,
+    ) -> (GlobalSliceMut<'_, I, T>, &mut T, GlobalSliceMut<'_, I, T>) {
+        let (left, right) = self.split_at_global_mut(index);
+        let (nth, right) = right.split_first_mut().expect("pivot out of bounds");
+        (left, nth, right)
+    }
+
+    /// Reorders the slice so that the element at `index` is at its sorted
+    /// position, returning the elements before it, the element itself
+    /// and the elements after it.
+    ///
+    /// Both sides keep the ids they have in `self`.
+    pub fn select_nth_unstable(
+        &mut self,
+        index: I,
+    ) -> (GlobalSliceMut<'_, I, T>, &mut T, GlobalSliceMut<'_, I, T>)
+    where
+        T: Ord,
+    {
+        self.inner.select_nth_unstable(index.as_index());
+        self.split_at_pivot_mut(index)
+    }
+
+    pub fn select_nth_unstable_by<F>(
+        &mut self,
+        index: I,
+        compare: F,
+    ) -> (GlobalSliceMut<'_, I, T>, &mut T, GlobalSliceMut<'_, I, T>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.inner.select_nth_unstable_by(index.as_index(), compare);
+        self.split_at_pivot_mut(index)
+    }
+
+    pub fn select_nth_unstable_by_key<K, F>(
+        &mut self,
+        index: I,
+        f: F,
+    ) -> (GlobalSliceMut<'_, I, T>, &mut T, GlobalSliceMut<'_, I, T>)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.inner.select_nth_unstable_by_key(index.as_index(), f);
+        self.split_at_pivot_mut(index)
+    }
+
     pub fn binary_search(&self, x: &T) -> Result<I, I>
     where
         T: Ord,
@@ -239,6 +740,153 @@ impl<I: TIndex, T> TSlice<I, T> {
             .map(I::from_index)
             .map_err(I::from_index)
     }
+
+    /// Returns the indices of `self` in the order in which the elements
+    /// would be after sorting, without moving any elements.
+    ///
+    /// Like `sort`, this is stable.
+    pub fn sorted_indices<J>(&self) -> TVec<J, I>
+    where
+        T: Ord,
+    {
+        self.sorted_indices_by(T::cmp)
+    }
+
+    pub fn sorted_indices_by<J, F>(&self, mut compare: F) -> TVec<J, I>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut indices: TVec<J, I> = self.index_iter().collect();
+        indices
+            .inner
+            .sort_by(|&a, &b| compare(&self.inner[a.as_index()], &self.inner[b.as_index()]));
+        indices
+    }
+
+    pub fn sorted_indices_by_key<J, K, F>(&self, mut f: F) -> TVec<J, I>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sorted_indices_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Like `sorted_indices`, but does not preserve the order of equal elements.
+    pub fn sorted_indices_unstable<J>(&self) -> TVec<J, I>
+    where
+        T: Ord,
+    {
+        self.sorted_indices_unstable_by(T::cmp)
+    }
+
+    pub fn sorted_indices_unstable_by<J, F>(&self, mut compare: F) -> TVec<J, I>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut indices: TVec<J, I> = self.index_iter().collect();
+        indices.inner.sort_unstable_by(|&a, &b| {
+            compare(&self.inner[a.as_index()], &self.inner[b.as_index()])
+        });
+        indices
+    }
+
+    pub fn sorted_indices_unstable_by_key<J, K, F>(&self, mut f: F) -> TVec<J, I>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sorted_indices_unstable_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<I>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.inner.iter().position(predicate).map(I::from_index)
+    }
+
+    pub fn rposition<P>(&self, predicate: P) -> Option<I>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.inner.iter().rposition(predicate).map(I::from_index)
+    }
+
+    /// Returns the index of the first element for which `pred` is false.
+    ///
+    /// Panics if `pred` holds for all elements and `self.len()` is not
+    /// representable by `I`, which is only possible if `self` contains
+    /// an element at `I::MAX_INDEX`.
+    pub fn partition_point<P>(&self, pred: P) -> I
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let idx = self.inner.partition_point(pred);
+        I::try_from_index(idx).unwrap_or_else(|| {
+            panic!(
+                "partition point {} is one past the largest representable index",
+                idx
+            )
+        })
+    }
+
+    /// Returns the first index whose element is not less than `x`,
+    /// assuming that `self` is sorted.
+    ///
+    /// Panics in the same cases as `partition_point`.
+    pub fn lower_bound(&self, x: &T) -> I
+    where
+        T: Ord,
+    {
+        self.partition_point(|e| e < x)
+    }
+
+    /// Returns the first index whose element is greater than `x`,
+    /// assuming that `self` is sorted.
+    ///
+    /// Panics in the same cases as `partition_point`.
+    pub fn upper_bound(&self, x: &T) -> I
+    where
+        T: Ord,
+    {
+        self.partition_point(|e| e <= x)
+    }
+
+    /// Returns the range of indices whose elements are equal to `x`,
+    /// assuming that `self` is sorted.
+    ///
+    /// Unlike `lower_bound` and `upper_bound` this never panics, as
+    /// the end of an `IndexRange` may be one past `I::MAX_INDEX`.
+    pub fn equal_range(&self, x: &T) -> IndexRange<I>
+    where
+        T: Ord,
+    {
+        let start = self.inner.partition_point(|e| e < x);
+        let end = start + self.inner[start..].partition_point(|e| e <= x);
+        IndexRange::from_usize(start, end)
+    }
+
+    /// Returns the index of the first minimum element.
+    pub fn argmin_by_key<K, F>(&self, mut f: F) -> Option<I>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.iter_enumerated()
+            .min_by_key(|(_, elem)| f(elem))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns the index of the last maximum element.
+    pub fn argmax_by_key<K, F>(&self, mut f: F) -> Option<I>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.iter_enumerated()
+            .max_by_key(|(_, elem)| f(elem))
+            .map(|(idx, _)| idx)
+    }
 }
 
 impl<'a, I, T> From<&'a [T]> for &'a TSlice<I, T> {
@@ -298,6 +946,102 @@ macro_rules! tvec {
     ($($x:expr,)*) => (tvec![$($x),*])
 }
 
+/// Implements `Add<usize>`, `Sub<usize>` and their assigning variants for
+/// index types, as well as `Sub<Self, Output = usize>`.
+///
+/// All operators panic if the result is not a valid index.
+#[macro_export]
+macro_rules! index_ops {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl ::std::ops::Add<usize> for $name {
+                type Output = Self;
+
+                #[inline]
+                fn add(self, rhs: usize) -> Self {
+                    $crate::TIndex::checked_add(self, rhs).expect("index overflow")
+                }
+            }
+
+            impl ::std::ops::AddAssign<usize> for $name {
+                #[inline]
+                fn add_assign(&mut self, rhs: usize) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl ::std::ops::Sub<usize> for $name {
+                type Output = Self;
+
+                #[inline]
+                fn sub(self, rhs: usize) -> Self {
+                    $crate::TIndex::checked_sub(self, rhs).expect("index underflow")
+                }
+            }
+
+            impl ::std::ops::SubAssign<usize> for $name {
+                #[inline]
+                fn sub_assign(&mut self, rhs: usize) {
+                    *self = *self - rhs;
+                }
+            }
+
+            impl ::std::ops::Sub for $name {
+                type Output = usize;
+
+                #[inline]
+                fn sub(self, rhs: Self) -> usize {
+                    $crate::TIndex::as_index(self)
+                        .checked_sub($crate::TIndex::as_index(rhs))
+                        .expect("index underflow")
+                }
+            }
+        )*
+    };
+}
+
+/// Declares newtype index types wrapping any other `TIndex` type.
+///
+/// ```
+/// tindex::define_index! {
+///     pub struct BlockId(u32);
+///     struct LocalBlockId(BlockId);
+/// }
+///
+/// let mut blocks = tindex::TVec::<BlockId, &str>::new();
+/// let id = blocks.push("entry");
+/// assert_eq!(format!("{:?}", id), "BlockId(0)");
+/// ```
+#[macro_export]
+macro_rules! define_index {
+    ($($(#[$attr:meta])* $vis:vis struct $name:ident($inner_vis:vis $inner:ty);)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            $vis struct $name($inner_vis $inner);
+
+            impl $crate::TIndex for $name {
+                const MAX_INDEX: usize = <$inner as $crate::TIndex>::MAX_INDEX;
+
+                #[inline]
+                fn as_index(self) -> usize {
+                    $crate::TIndex::as_index(self.0)
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    $name(<$inner as $crate::TIndex>::from_index(index))
+                }
+
+                #[inline]
+                fn try_from_index(index: usize) -> Option<Self> {
+                    <$inner as $crate::TIndex>::try_from_index(index).map($name)
+                }
+            }
+        )*
+    };
+}
+
 impl<I, T: fmt::Debug> fmt::Debug for TVec<I, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter()).finish()
@@ -371,10 +1115,52 @@ impl<I, T> TVec<I, T> {
         }
     }
 
+    pub fn as_vec(&self) -> &Vec<T> {
+        &self.inner
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner
+    }
+
+    pub fn into_boxed_slice(self) -> Box<TSlice<I, T>> {
+        let ptr = Box::into_raw(self.inner.into_boxed_slice());
+        unsafe {
+            // SAFETY: as `TSlice` is `#[repr(transparent)]`
+            // casting from `[T]` to `TSlice<I, T>` is safe
+            Box::from_raw(ptr as *mut TSlice<I, T>)
+        }
+    }
+
+    /// Reinterprets `self` as a vector indexed by `J`, without moving any elements.
+    ///
+    /// Ids are converted by their numeric value, so this should only be used
+    /// if both index types deliberately share a numbering. The other
+    /// `cast_index` methods of this crate work the same way.
+    pub fn cast_index<J>(self) -> TVec<J, T> {
+        TVec::from_vec(self.inner)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         self.inner.pop()
     }
 
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&mut T) -> bool,
@@ -397,6 +1183,98 @@ impl<I, T> TVec<I, T> {
         }
     }
 
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.inner.retain_mut(f)
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.inner.dedup()
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.inner.dedup_by_key(key)
+    }
+
+    /// Maps each element of `self`, keeping the indices unchanged.
+    ///
+    /// If `T` and `U` have the same size and alignment, this reuses the
+    /// allocation of `self`.
+    pub fn map_in_place<U, F>(self, mut f: F) -> TVec<I, U>
+    where
+        F: FnMut(T) -> U,
+    {
+        if Layout::new::<T>() != Layout::new::<U>() {
+            return self.inner.into_iter().map(f).collect();
+        }
+
+        /// Cleans up a partially mapped buffer if `f` panics.
+        struct Guard<T, U> {
+            ptr: *mut T,
+            len: usize,
+            cap: usize,
+            mapped: usize,
+            _marker: PhantomData<U>,
+        }
+
+        impl<T, U> Drop for Guard<T, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    // SAFETY: the first `mapped` elements have been replaced by
+                    // values of type `U`, the element at `mapped` has been moved
+                    // out, and all following ones are still initialized.
+                    let mapped = ptr::slice_from_raw_parts_mut(self.ptr as *mut U, self.mapped);
+                    ptr::drop_in_place(mapped);
+                    let rest = ptr::slice_from_raw_parts_mut(
+                        self.ptr.add(self.mapped + 1),
+                        self.len - self.mapped - 1,
+                    );
+                    ptr::drop_in_place(rest);
+                    drop(Vec::from_raw_parts(self.ptr, 0, self.cap));
+                }
+            }
+        }
+
+        let mut inner = ManuallyDrop::new(self.inner);
+        let mut guard = Guard::<T, U> {
+            ptr: inner.as_mut_ptr(),
+            len: inner.len(),
+            cap: inner.capacity(),
+            mapped: 0,
+            _marker: PhantomData,
+        };
+        while guard.mapped < guard.len {
+            unsafe {
+                // SAFETY: `T` and `U` have the same layout, so each element
+                // can be replaced in place once it has been read.
+                let elem = guard.ptr.add(guard.mapped);
+                let value = f(elem.read());
+                (elem as *mut U).write(value);
+            }
+            guard.mapped += 1;
+        }
+
+        let guard = ManuallyDrop::new(guard);
+        unsafe {
+            // SAFETY: all elements have been replaced by values of type `U`
+            // and the allocation has the same layout for `U` as for `T`.
+            TVec::from_vec(Vec::from_raw_parts(
+                guard.ptr as *mut U,
+                guard.len,
+                guard.cap,
+            ))
+        }
+    }
+
     pub fn resize(&mut self, new_len: usize, value: T)
     where
         T: Clone,
@@ -404,6 +1282,13 @@ impl<I, T> TVec<I, T> {
         self.inner.resize(new_len, value)
     }
 
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.inner.resize_with(new_len, f)
+    }
+
     pub fn extend_from_slice(&mut self, other: &TSlice<I, T>)
     where
         T: Clone,
@@ -419,41 +1304,173 @@ impl<I, T> TVec<I, T> {
 impl<I: TIndex, T> TVec<I, T> {
     pub fn push(&mut self, item: T) -> I {
         let idx = self.inner.len();
+        let id = I::try_from_index(idx)
+            .unwrap_or_else(|| panic!("index {} overflows in `TVec::push`", idx));
+        self.inner.push(item);
+        id
+    }
+
+    /// Pushes `item`, returning an error instead if its index would not fit
+    /// into `I`. In this case `item` is dropped.
+    pub fn try_push(&mut self, item: T) -> Result<I, IndexOverflow<I>> {
+        let idx = self.inner.len();
+        let id = I::try_from_index(idx).ok_or_else(|| IndexOverflow::new(idx))?;
         self.inner.push(item);
-        I::from_index(idx)
+        Ok(id)
+    }
+
+    /// Returns the id the next pushed element will have.
+    pub fn next_id(&self) -> I {
+        I::from_index(self.inner.len())
+    }
+
+    /// Pushes the result of `f`, which is given the id of the new element.
+    pub fn push_with<F>(&mut self, f: F) -> I
+    where
+        F: FnOnce(I) -> T,
+    {
+        let id = self.next_id();
+        self.inner.push(f(id));
+        id
+    }
+
+    /// Extends `self` with the elements of `iter`, returning the ids
+    /// assigned to them.
+    pub fn extend_with_ids<U: IntoIterator<Item = T>>(&mut self, iter: U) -> IndexRange<I> {
+        let start = self.inner.len();
+        self.inner.extend(iter);
+        let end = self.inner.len();
+        debug_assert!(
+            end == start || end - 1 <= I::MAX_INDEX,
+            "index overflow in `TVec::extend_with_ids`"
+        );
+        IndexRange::from_usize(start, end)
+    }
+
+    /// Grows `self` using `fill` until it contains `id`, returning the element at `id`.
+    pub fn ensure_contains_elem<F>(&mut self, id: I, fill: F) -> &mut T
+    where
+        F: FnMut() -> T,
+    {
+        let idx = id.as_index();
+        if idx >= self.inner.len() {
+            self.inner.resize_with(idx + 1, fill);
+        }
+        &mut self.inner[idx]
+    }
+
+    /// Stores `value` at `id`, growing `self` using `fill` if necessary.
+    ///
+    /// Unlike `insert`, this replaces the previous element at `id` instead of
+    /// shifting all following elements.
+    pub fn insert_at_id<F>(&mut self, id: I, value: T, fill: F) -> &mut T
+    where
+        F: FnMut() -> T,
+    {
+        let idx = id.as_index();
+        if idx < self.inner.len() {
+            self.inner[idx] = value;
+        } else {
+            self.inner.resize_with(idx, fill);
+            self.inner.push(value);
+        }
+        &mut self.inner[idx]
     }
 
     pub fn insert(&mut self, idx: I, elem: T) {
         self.inner.insert(idx.as_index(), elem)
     }
 
+    /// Inserts `elem` at `idx`, returning an error instead if the last element
+    /// would be moved to an index which does not fit into `I`.
+    pub fn try_insert(&mut self, idx: I, elem: T) -> Result<(), IndexOverflow<I>> {
+        let last = self.inner.len();
+        if last > I::MAX_INDEX {
+            return Err(IndexOverflow::new(last));
+        }
+        self.inner.insert(idx.as_index(), elem);
+        Ok(())
+    }
+
+    /// Extends `self` with the elements of `iter`, stopping at the first element
+    /// whose index would not fit into `I`. All previous elements remain pushed.
+    pub fn try_extend<U: IntoIterator<Item = T>>(
+        &mut self,
+        iter: U,
+    ) -> Result<(), IndexOverflow<I>> {
+        for item in iter {
+            self.try_push(item)?;
+        }
+        Ok(())
+    }
+
     pub fn remove(&mut self, id: I) -> T {
         self.inner.remove(id.as_index())
     }
 
+    /// Removes the element at `id`, replacing it with the last element.
+    ///
+    /// Also returns the previous id of the moved element, which is `None`
+    /// if `id` was the last element itself.
+    pub fn swap_remove(&mut self, id: I) -> (T, Option<I>) {
+        let idx = id.as_index();
+        let last = self.inner.len().wrapping_sub(1);
+        let elem = self.inner.swap_remove(idx);
+        let moved = if idx == last {
+            None
+        } else {
+            Some(I::from_index(last))
+        };
+        (elem, moved)
+    }
+
+    /// Shortens `self` to only contain the elements before `len`.
+    pub fn truncate(&mut self, len: I) {
+        self.inner.truncate(len.as_index())
+    }
+
+    pub fn drain<R: RangeBounds<I>>(&mut self, range: R) -> Drain<'_, T> {
+        self.inner.drain(slice_index::bounds_as_usize(&range))
+    }
+
+    /// Removes and yields all elements in `range` for which `filter` returns `true`.
+    pub fn extract_if<R, F>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F>
+    where
+        R: RangeBounds<I>,
+        F: FnMut(&mut T) -> bool,
+    {
+        self.inner
+            .extract_if(slice_index::bounds_as_usize(&range), filter)
+    }
+
     pub fn splice<R, E>(&mut self, range: R, replace_with: E) -> Splice<'_, E::IntoIter>
     where
         R: RangeBounds<I>,
         E: IntoIterator<Item = T>,
     {
-        let start = match range.start_bound() {
-            Bound::Included(v) => Bound::Included(v.as_index()),
-            Bound::Excluded(v) => Bound::Excluded(v.as_index()),
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        let end = match range.end_bound() {
-            Bound::Included(v) => Bound::Included(v.as_index()),
-            Bound::Excluded(v) => Bound::Excluded(v.as_index()),
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        self.inner.splice((start, end), replace_with)
+        self.inner
+            .splice(slice_index::bounds_as_usize(&range), replace_with)
     }
 
     pub fn split_off(&mut self, at: I) -> Self {
         self.inner.split_off(at.as_index()).into()
     }
+
+    pub fn into_iter_enumerated(self) -> Enumerated<I, IntoIter<T>> {
+        Enumerated::new(self.inner.into_iter())
+    }
+}
+
+impl<I: TIndex, T> TVec<I, Option<T>> {
+    /// Returns the value at `id`, computing it using `f` if it is missing.
+    ///
+    /// Grows `self` with `None` if it does not yet contain `id`.
+    pub fn get_or_insert_with<F>(&mut self, id: I, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.ensure_contains_elem(id, || None).get_or_insert_with(f)
+    }
 }
 
 impl<'a, I, T: Clone> From<&'a TSlice<I, T>> for TVec<I, T> {
@@ -517,6 +1534,25 @@ impl<I, T> From<Vec<T>> for TVec<I, T> {
     }
 }
 
+impl<I, T> From<TVec<I, T>> for Vec<T> {
+    #[inline]
+    fn from(vec: TVec<I, T>) -> Self {
+        vec.inner
+    }
+}
+
+impl<I, T> AsRef<[T]> for TSlice<I, T> {
+    fn as_ref(&self) -> &[T] {
+        &self.inner
+    }
+}
+
+impl<I, T> AsMut<[T]> for TSlice<I, T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.inner
+    }
+}
+
 impl<I, T> Extend<T> for TVec<I, T> {
     fn extend<U: IntoIterator<Item = T>>(&mut self, iter: U) {
         self.inner.extend(iter)
@@ -563,13 +1599,13 @@ impl<I, T> FromIterator<T> for TVec<I, T> {
 impl<I, T> Deref for TVec<I, T> {
     type Target = TSlice<I, T>;
 
-    fn deref<'a>(&'a self) -> &'a TSlice<I, T> {
+    fn deref(&self) -> &TSlice<I, T> {
         self.inner.deref().into()
     }
 }
 
 impl<I, T> DerefMut for TVec<I, T> {
-    fn deref_mut<'a>(&'a mut self) -> &'a mut TSlice<I, T> {
+    fn deref_mut(&mut self) -> &mut TSlice<I, T> {
         self.inner.deref_mut().into()
     }
 }
@@ -585,3 +1621,485 @@ impl<I, T> AsMut<TSlice<I, T>> for TVec<I, T> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    define_index! {
+        struct BlockId(u32);
+        struct LocalId(BlockId);
+        struct SmallId(NonZeroU8);
+    }
+
+    index_ops!(BlockId, SmallId);
+
+    #[test]
+    fn define_index() {
+        let mut v: TVec<LocalId, u8> = tvec![7, 8];
+        let id = v.push(9);
+        assert_eq!(id, LocalId(BlockId(2)));
+        assert_eq!(id.as_index(), 2);
+        assert_eq!(v[id], 9);
+        assert!(LocalId::from_index(1) < id);
+        assert_eq!(format!("{:?}", BlockId(3)), "BlockId(3)");
+        assert_eq!(format!("{:?}", id), "LocalId(BlockId(2))");
+    }
+
+    #[test]
+    fn try_push() {
+        let mut v: TVec<NonMaxU8, u8> = (0..254).collect();
+        assert_eq!(v.try_push(254).ok(), NonMaxU8::new(254));
+        assert_eq!(v.try_push(255), Err(IndexOverflow::new(255)));
+        assert_eq!(
+            v.try_insert(NonMaxU8::ZERO, 0),
+            Err(IndexOverflow::new(255))
+        );
+        assert_eq!(v.len(), 255);
+
+        let mut v: TVec<NonMaxU8, u8> = TVec::new();
+        assert_eq!(v.try_extend(0..=255).unwrap_err().index(), 255);
+        assert_eq!(v.len(), 255);
+        assert_eq!(v.last(), Some(&254));
+    }
+
+    #[test]
+    fn push_overflow() {
+        use std::panic;
+
+        let mut v: TVec<u8, u8> = (0..=255).collect();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| v.push(0)));
+        assert!(result.is_err());
+        assert_eq!(v.len(), 256);
+    }
+
+    #[test]
+    fn primitive_indices() {
+        assert_eq!(u8::MAX_INDEX, 255);
+        assert_eq!(u16::try_from_index(65536), None);
+        assert_eq!(u64::from_index(7), 7);
+        assert_eq!(NonZeroU8::MAX_INDEX, 254);
+        assert_eq!(NonZeroU32::from_index(0).get(), 1);
+        assert_eq!(NonZeroU16::new(5).unwrap().as_index(), 4);
+        assert_eq!(NonZeroU8::try_from_index(255), None);
+
+        let mut v: TVec<NonZeroU8, char> = TVec::new();
+        let a = v.push('a');
+        let b = v.push('b');
+        assert_eq!(a.get(), 1);
+        assert_eq!(v[b], 'b');
+        assert_eq!(v.index_iter().collect::<Vec<_>>(), vec![a, b]);
+        let set: TBitSet<NonZeroU8> = v.index_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![a, b]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_index_overflow() {
+        u8::from_index(256);
+    }
+
+    #[test]
+    fn index_arithmetic() {
+        let id = BlockId(3);
+        assert_eq!(id.next(), Some(BlockId(4)));
+        assert_eq!(id.prev(), Some(BlockId(2)));
+        assert_eq!(BlockId(0).prev(), None);
+        assert_eq!(BlockId(u32::MAX).next(), None);
+        assert_eq!(id.offset(-3), Some(BlockId(0)));
+        assert_eq!(id.offset(-4), None);
+        assert_eq!(id.distance_to(BlockId(1)), -2);
+        assert_eq!(id.offset(id.distance_to(BlockId(10))), Some(BlockId(10)));
+        assert_eq!(TIndex::checked_add(7u8, 248), Some(255));
+        assert_eq!(TIndex::checked_add(7u8, 249), None);
+
+        let max = SmallId::from_index(SmallId::MAX_INDEX);
+        assert_eq!(max.next(), None);
+        assert_eq!(max.checked_sub(254), Some(SmallId::from_index(0)));
+
+        let mut id = id + 2;
+        assert_eq!(id, BlockId(5));
+        id -= 5;
+        assert_eq!(id, BlockId(0));
+        assert_eq!(BlockId(7) - BlockId(4), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_ops_overflow() {
+        let _ = SmallId::from_index(SmallId::MAX_INDEX) + 1;
+    }
+
+    #[test]
+    fn iter_enumerated() {
+        let mut v: TVec<BlockId, char> = tvec!['a', 'b', 'c', 'd'];
+        assert_eq!(
+            v.iter_enumerated()
+                .rev()
+                .skip(1)
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![(BlockId(2), &'c'), (BlockId(1), &'b')]
+        );
+        assert_eq!(v.iter_enumerated().len(), 4);
+
+        for (id, c) in v.iter_enumerated_mut().skip(2) {
+            *c = char::from(b'0' + id.0 as u8);
+        }
+        assert_eq!(v, tvec!['a', 'b', '2', '3']);
+
+        let mut iter = v.into_iter_enumerated();
+        assert_eq!(iter.next_back(), Some((BlockId(3), '3')));
+        assert_eq!(iter.nth(1), Some((BlockId(1), 'b')));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(BlockId(2), '2')]);
+    }
+
+    #[test]
+    fn search() {
+        let v: TVec<BlockId, u32> = tvec![1, 3, 3, 3, 7, 9];
+        assert_eq!(v.position(|&x| x == 3), Some(BlockId(1)));
+        assert_eq!(v.rposition(|&x| x == 3), Some(BlockId(3)));
+        assert_eq!(v.position(|&x| x == 4), None);
+        assert_eq!(v.partition_point(|&x| x < 8), BlockId(5));
+        assert_eq!(v.lower_bound(&3), BlockId(1));
+        assert_eq!(v.upper_bound(&3), BlockId(4));
+        assert_eq!(v.equal_range(&3), IndexRange::new(BlockId(1), BlockId(4)));
+        assert!(v.equal_range(&5).is_empty());
+        assert_eq!(v.argmin_by_key(|&x| x.abs_diff(3)), Some(BlockId(1)));
+        assert_eq!(v.argmax_by_key(|&x| x % 3), Some(BlockId(4)));
+        assert_eq!(TVec::<BlockId, u32>::new().argmax_by_key(|&x| x), None);
+    }
+
+    #[test]
+    fn search_full_index_range() {
+        let v: TVec<u8, u8> = (0..=255).collect();
+        assert_eq!(v.lower_bound(&255), 255);
+        let range = v.equal_range(&255);
+        assert_eq!(range.len(), 1);
+        assert_eq!(range.start(), 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "one past the largest representable index")]
+    fn upper_bound_overflow() {
+        let v: TVec<u8, u8> = (0..=255).collect();
+        v.upper_bound(&255);
+    }
+
+    #[test]
+    fn split_first_last() {
+        let mut v: TVec<BlockId, u32> = tvec![1, 2, 3];
+        assert_eq!(v.first_id(), Some(BlockId(0)));
+        assert_eq!(TVec::<BlockId, u32>::new().first_id(), None);
+
+        let (first, rest) = v.split_first().unwrap();
+        assert_eq!((first, rest.as_local().to_slice()), (&1, &[2, 3][..]));
+        assert_eq!(rest[BlockId(1)], 2);
+
+        let (id, _, rest) = v.split_first_id().unwrap();
+        let (next, _, rest) = rest.split_first_id().unwrap();
+        let (last, elem, rest) = rest.split_first_id().unwrap();
+        assert_eq!((id, next, last), (BlockId(0), BlockId(1), BlockId(2)));
+        assert_eq!(elem, &3);
+        assert!(rest.is_empty());
+
+        let (id, last, rest) = v.split_last_id().unwrap();
+        assert_eq!((id, last), (BlockId(2), &3));
+        assert_eq!(rest.split_last_id().map(|(id, _, _)| id), Some(BlockId(1)));
+
+        let (id, last, rest) = v.split_last_id_mut().unwrap();
+        *last += rest[BlockId(0)];
+        rest[BlockId(1)] = 0;
+        assert_eq!(id, BlockId(2));
+        assert_eq!(v, tvec![1, 0, 4]);
+
+        let mut sum = 0;
+        let mut ids = Vec::new();
+        let mut rest = v.as_global_mut();
+        while let Some((id, first, tail)) = rest.split_first_id_mut() {
+            ids.push(id);
+            sum += *first;
+            *first = sum;
+            rest = tail;
+        }
+        assert_eq!(ids, vec![BlockId(0), BlockId(1), BlockId(2)]);
+        assert_eq!(v, tvec![1, 1, 5]);
+    }
+
+    #[test]
+    fn get_many_mut() {
+        let mut v: TVec<BlockId, u32> = tvec![0, 1, 2, 3, 4];
+        let [a, b, c] = v
+            .get_many_mut([BlockId(4), BlockId(0), BlockId(2)])
+            .unwrap();
+        std::mem::swap(a, b);
+        *c = 7;
+        assert_eq!(v, tvec![4, 1, 7, 3, 0]);
+        assert!(v.get_many_mut([BlockId(1), BlockId(1)]).is_none());
+        assert!(v.get_many_mut([BlockId(1), BlockId(5)]).is_none());
+
+        let (a, b) = v.pick2_mut(BlockId(3), BlockId(1));
+        *a += *b;
+        assert_eq!(v[BlockId(3)], 4);
+
+        let [left, right] = v
+            .get_many_ranges_mut([BlockId(3)..BlockId(5), BlockId(0)..BlockId(2)])
+            .unwrap();
+        left.swap(BlockId(0), BlockId(1));
+        right[BlockId(1)] = 9;
+        assert_eq!(v, tvec![4, 9, 7, 0, 4]);
+        assert!(v
+            .get_many_ranges_mut([BlockId(0)..BlockId(2), BlockId(1)..BlockId(3)])
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn pick2_mut_same_index() {
+        let mut v: TVec<BlockId, u32> = tvec![0, 1];
+        v.pick2_mut(BlockId(1), BlockId(1));
+    }
+
+    #[test]
+    fn get_unchecked() {
+        let mut v: TVec<BlockId, u32> = tvec![0, 1, 2, 3];
+        for id in v.index_iter() {
+            unsafe {
+                *v.get_unchecked_mut(id) += 1;
+            }
+        }
+        unsafe {
+            assert_eq!(*v.get_unchecked(BlockId(3)), 4);
+            assert_eq!(v.get_unchecked(BlockId(1)..BlockId(3)).to_slice(), &[2, 3]);
+            assert_eq!(
+                v.get_unchecked(BlockId(1)..=BlockId(3)).to_slice(),
+                &[2, 3, 4]
+            );
+            assert_eq!(v.get_unchecked(..=BlockId(0)).to_slice(), &[1]);
+            assert_eq!(v.get_unchecked(BlockId(4)..).len(), 0);
+            v.get_unchecked_mut(..BlockId(2))
+                .swap(BlockId(0), BlockId(1));
+        }
+        assert_eq!(v, tvec![2, 1, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn get_unchecked_out_of_bounds() {
+        let v: TVec<BlockId, u32> = tvec![0, 1, 2, 3];
+        unsafe {
+            v.get_unchecked(BlockId(2)..BlockId(5));
+        }
+    }
+
+    #[test]
+    fn bound_index() {
+        use std::ops::Bound::*;
+
+        let mut v: TVec<BlockId, u32> = tvec![0, 1, 2, 3];
+        assert_eq!(
+            v[(Excluded(BlockId(0)), Included(BlockId(2)))].to_slice(),
+            &[1, 2]
+        );
+        assert_eq!(
+            v.get((Unbounded, Excluded(BlockId(1))))
+                .map(|s| s.to_slice()),
+            Some(&[0][..])
+        );
+        assert_eq!(v.get((Included(BlockId(2)), Included(BlockId(4)))), None);
+        assert_eq!(v.get(BlockId(3)), Some(&3));
+        assert_eq!(v.get(BlockId(4)), None);
+        assert_eq!(v.get(BlockId(3)..BlockId(5)), None);
+        assert_eq!(v.get(BlockId(3)..BlockId(2)), None);
+        v.get_mut((Excluded(BlockId(1)), Unbounded)).unwrap()[BlockId(1)] = 7;
+        assert_eq!(v, tvec![0, 1, 2, 7]);
+        unsafe {
+            assert_eq!(
+                v.get_unchecked((Excluded(BlockId(2)), Unbounded))
+                    .to_slice(),
+                &[7]
+            );
+        }
+    }
+
+    #[test]
+    fn sorted_indices() {
+        let v: TVec<BlockId, (u32, char)> = tvec![(3, 'a'), (1, 'b'), (3, 'c'), (0, 'd')];
+        let order: TVec<usize, BlockId> = v.sorted_indices();
+        assert_eq!(order, tvec![BlockId(3), BlockId(1), BlockId(0), BlockId(2)]);
+        let order: TVec<usize, BlockId> = v.sorted_indices_by_key(|&(k, _)| std::cmp::Reverse(k));
+        assert_eq!(order, tvec![BlockId(0), BlockId(2), BlockId(1), BlockId(3)]);
+        let order: TVec<usize, BlockId> = v.sorted_indices_unstable_by_key(|&(k, _)| k);
+        assert_eq!(order[0], BlockId(3));
+        assert!(matches!(order[3], BlockId(0) | BlockId(2)));
+        assert_eq!(v[BlockId(0)], (3, 'a'));
+    }
+
+    #[test]
+    fn in_place() {
+        let mut v: TVec<BlockId, u32> = tvec![4, 2, 5, 1, 3];
+        let (left, median, right) = v.select_nth_unstable(BlockId(2));
+        assert_eq!(*median, 3);
+        assert!(left.iter().all(|&x| x < 3));
+        assert!(right.iter().all(|&x| x > 3));
+        assert_eq!(right.start(), BlockId(3));
+        assert!(right.get(BlockId(4)).is_some());
+
+        v.sort_unstable();
+        assert_eq!(v, tvec![1, 2, 3, 4, 5]);
+        v.rotate_left(BlockId(2));
+        assert_eq!(v, tvec![3, 4, 5, 1, 2]);
+        v.rotate_right(BlockId(1));
+        v.reverse();
+        assert_eq!(v, tvec![1, 5, 4, 3, 2]);
+        assert!(v.starts_with(&tvec![1, 5]));
+        assert!(v.ends_with(&tvec![3, 2]));
+
+        let mut w: TVec<BlockId, u32> = tvec![0; 5];
+        w.swap_with_slice(&mut v);
+        assert_eq!(v, tvec![0; 5]);
+        v[BlockId(1)..].copy_from_slice(&w[..BlockId(4)]);
+        assert_eq!(v, tvec![0, 1, 5, 4, 3]);
+        v.fill(7);
+        let mut n = 0;
+        w.fill_with(|| {
+            n += 1;
+            n
+        });
+        v.clone_from_slice(&w);
+        assert_eq!(v, tvec![1, 2, 3, 4, 5]);
+        v.sort_unstable_by_key(|&x| std::cmp::Reverse(x));
+        assert_eq!(v.last(), Some(&1));
+    }
+
+    #[test]
+    fn map() {
+        let v: TVec<BlockId, u32> = tvec![1, 2, 3];
+        let doubled: TVec<BlockId, u64> = v.map(|&x| x as u64 * 2);
+        assert_eq!(doubled, tvec![2, 4, 6]);
+        assert_eq!(v.map_enumerated(|id, &x| id.0 + x), tvec![1, 3, 5]);
+        assert_eq!(v.try_map(|&x| u8::try_from(x)), Ok(tvec![1, 2, 3]));
+        assert_eq!(
+            v.try_map(|&x| if x < 2 { Ok(x) } else { Err(x) }),
+            Err((BlockId(1), 2))
+        );
+
+        let ptr = v.to_slice().as_ptr() as usize;
+        let signed: TVec<BlockId, i32> = v.map_in_place(|x| -(x as i32));
+        assert_eq!(signed, tvec![-1, -2, -3]);
+        assert_eq!(signed.to_slice().as_ptr() as usize, ptr);
+        let wide: TVec<BlockId, i64> = signed.map_in_place(i64::from);
+        assert_eq!(wide, tvec![-1, -2, -3]);
+    }
+
+    #[test]
+    fn map_in_place_panic() {
+        use std::{panic, rc::Rc};
+
+        let counter = Rc::new(());
+        let v: TVec<BlockId, Rc<()>> = tvec![counter.clone(); 4];
+        let mut calls = 0;
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            v.map_in_place(|rc| {
+                calls += 1;
+                assert!(calls < 3, "stop");
+                rc
+            })
+        }));
+        assert_eq!(calls, 3);
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn conversions() {
+        let mut v: TVec<BlockId, u32> = tvec![1, 2, 3];
+        v.as_mut_slice()[0] = 0;
+        assert_eq!(v.as_vec(), &vec![0, 2, 3]);
+        let borrowed = &v;
+        assert_eq!(borrowed.cast_index_ref::<LocalId>()[LocalId(BlockId(2))], 3);
+        v.cast_index_mut::<usize>()[1] = 4;
+
+        let local: TVec<LocalId, u32> = v.clone().cast_index();
+        assert_eq!(local[LocalId(BlockId(1))], 4);
+        let boxed = local.into_boxed_slice();
+        assert_eq!(boxed[LocalId(BlockId(0))], 0);
+        assert_eq!(Vec::from(v.clone()), vec![0, 4, 3]);
+        assert_eq!(v.into_vec(), vec![0, 4, 3]);
+    }
+
+    #[test]
+    fn vec_api() {
+        let mut v: TVec<BlockId, u32> = tvec![0, 1, 1, 2, 3, 3, 3, 4];
+        v.dedup();
+        assert_eq!(v, tvec![0, 1, 2, 3, 4]);
+        assert_eq!(v.swap_remove(BlockId(1)), (1, Some(BlockId(4))));
+        assert_eq!(v.swap_remove(BlockId(3)), (3, None));
+        assert_eq!(v, tvec![0, 4, 2]);
+
+        v.resize_with(6, || 7);
+        let drained: Vec<_> = v.drain(BlockId(1)..=BlockId(2)).collect();
+        assert_eq!(drained, vec![4, 2]);
+        let extracted: Vec<_> = v.extract_if(BlockId(1).., |x| *x == 7).collect();
+        assert_eq!(extracted, vec![7, 7, 7]);
+        assert_eq!(v, tvec![0]);
+
+        let mut v: TVec<BlockId, u32> = tvec![1, 2, 4, 5, 8];
+        v.dedup_by_key(|x| *x / 2);
+        v.retain_mut(|x| {
+            *x += 1;
+            *x < 9
+        });
+        assert_eq!(v, tvec![2, 3, 5]);
+        v.truncate(BlockId(1));
+        assert_eq!(v, tvec![2]);
+        v.clear();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn push_with() {
+        let mut v: TVec<BlockId, (BlockId, Option<BlockId>)> = TVec::new();
+        assert_eq!(v.next_id(), BlockId(0));
+        let a = v.push_with(|id| (id, None));
+        let b = v.push_with(|id| (id, Some(a)));
+        assert_eq!(v[b], (BlockId(1), Some(BlockId(0))));
+
+        let start = v.next_id();
+        let ids = v.extend_with_ids((0..3).map(|i| (start + i, Some(start))));
+        assert_eq!(ids, IndexRange::new(BlockId(2), BlockId(5)));
+        assert!(ids.clone().all(|id| v[id].0 == id));
+        assert!(v.extend_with_ids(None).is_empty());
+    }
+
+    #[test]
+    fn auto_growing() {
+        let mut v: TVec<BlockId, u32> = tvec![1];
+        *v.ensure_contains_elem(BlockId(2), || 0) += 5;
+        assert_eq!(v, tvec![1, 0, 5]);
+        assert_eq!(*v.ensure_contains_elem(BlockId(0), || unreachable!()), 1);
+        assert_eq!(*v.insert_at_id(BlockId(1), 3, || unreachable!()), 3);
+        v.insert_at_id(BlockId(4), 4, || 9);
+        assert_eq!(v, tvec![1, 3, 5, 9, 4]);
+
+        let mut calls = 0;
+        let mut w: TVec<BlockId, u32> = TVec::new();
+        w.insert_at_id(BlockId(3), 9, || {
+            calls += 1;
+            0
+        });
+        assert_eq!(calls, 3);
+        assert_eq!(w, tvec![0, 0, 0, 9]);
+
+        let mut names: TVec<BlockId, Option<String>> = TVec::new();
+        names
+            .get_or_insert_with(BlockId(1), || "b".into())
+            .push('!');
+        assert_eq!(
+            names.get_or_insert_with(BlockId(1), || unreachable!()),
+            "b!"
+        );
+        assert_eq!(names, tvec![None, Some("b!".to_string())]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Index representations which reserve their maximum value as a niche,
+//! so that `Option<I>` has the same size as `I`.
+use std::{
+    cmp::Ordering,
+    fmt,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+};
+
+use crate::{max_index, TIndex};
+
+macro_rules! non_max {
+    ($($name:ident($non_zero:ident, $int:ident);)*) => {
+        $(
+            /// An integer which is known to not equal its maximum value.
+            ///
+            /// The value is stored bitwise inverted in a non zero integer.
+            #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+            #[repr(transparent)]
+            pub struct $name($non_zero);
+
+            impl $name {
+                pub const ZERO: Self = match Self::new(0) {
+                    Some(v) => v,
+                    None => unreachable!(),
+                };
+
+                pub const MAX: Self = match Self::new($int::MAX - 1) {
+                    Some(v) => v,
+                    None => unreachable!(),
+                };
+
+                #[inline]
+                pub const fn new(value: $int) -> Option<Self> {
+                    match $non_zero::new(!value) {
+                        Some(v) => Some(Self(v)),
+                        None => None,
+                    }
+                }
+
+                #[inline]
+                pub const fn get(self) -> $int {
+                    !self.0.get()
+                }
+            }
+
+            impl PartialOrd for $name {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for $name {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.get().cmp(&other.get())
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.get(), f)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.get(), f)
+                }
+            }
+
+            impl TIndex for $name {
+                const MAX_INDEX: usize = max_index($int::MAX as u128 - 1);
+
+                #[inline]
+                fn as_index(self) -> usize {
+                    self.get().as_index()
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    Self::try_from_index(index).unwrap_or_else(|| {
+                        panic!("index {} out of range for `{}`", index, stringify!($name))
+                    })
+                }
+
+                #[inline]
+                fn try_from_index(index: usize) -> Option<Self> {
+                    $int::try_from(index).ok().and_then(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+non_max! {
+    NonMaxU8(NonZeroU8, u8);
+    NonMaxU16(NonZeroU16, u16);
+    NonMaxU32(NonZeroU32, u32);
+    NonMaxU64(NonZeroU64, u64);
+    NonMaxUsize(NonZeroUsize, usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+    use crate::{TBitSet, TVec};
+
+    crate::define_index! {
+        struct NodeId(NonMaxU32);
+    }
+
+    #[test]
+    fn niche() {
+        assert_eq!(mem::size_of::<Option<NonMaxU32>>(), 4);
+        assert_eq!(mem::size_of::<Option<NodeId>>(), 4);
+        assert_eq!(mem::size_of::<Option<NonMaxU8>>(), 1);
+        assert_eq!(NonMaxU8::new(u8::MAX), None);
+        assert_eq!(NonMaxU8::MAX.get(), u8::MAX - 1);
+        assert_eq!(NonMaxU32::ZERO.get(), 0);
+        assert!(NonMaxU16::new(3) < NonMaxU16::new(4));
+        assert_eq!(format!("{:?}", NodeId::from_index(7)), "NodeId(7)");
+    }
+
+    #[test]
+    fn containers() {
+        let mut parents: TVec<NodeId, Option<NodeId>> = TVec::new();
+        let root = parents.push(None);
+        let child = parents.push(Some(root));
+        assert_eq!(parents[child], Some(root));
+        assert_eq!(parents.index_iter().collect::<Vec<_>>(), vec![root, child]);
+
+        let set: TBitSet<NodeId> = parents.index_iter().skip(1).collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![child]);
+    }
+
+    #[test]
+    fn max_index() {
+        assert_eq!(NonMaxU8::MAX_INDEX, 254);
+        assert_eq!(NodeId::MAX_INDEX, u32::MAX as usize - 1);
+        assert_eq!(NodeId::try_from_index(u32::MAX as usize), None);
+        assert_eq!(NonMaxU8::try_from_index(254), NonMaxU8::new(254));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_index_overflow() {
+        NonMaxU8::from_index(u8::MAX as usize);
+    }
+}
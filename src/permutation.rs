@@ -0,0 +1,178 @@
+use std::{fmt, ops::Index};
+
+use crate::{TBitSet, TIndex, TSlice, TVec};
+
+/// A bijective mapping from the indices `0..len` onto themselves.
+///
+/// Applying a permutation moves the element at `old` to `perm[old]`.
+pub struct Permutation<I> {
+    inner: TVec<I, I>,
+}
+
+impl<I: Clone> Clone for Permutation<I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I: PartialEq> PartialEq for Permutation<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<I: Eq> Eq for Permutation<I> {}
+
+impl<I: fmt::Debug> fmt::Debug for Permutation<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Permutation").field(&self.inner).finish()
+    }
+}
+
+impl<I: TIndex> Permutation<I> {
+    pub fn identity(len: usize) -> Self {
+        Self {
+            inner: (0..len).map(I::from_index).collect(),
+        }
+    }
+
+    /// Creates a permutation moving the element at `old` to `mapping[old]`.
+    ///
+    /// Returns `None` if `mapping` is not a bijection.
+    pub fn new(mapping: TVec<I, I>) -> Option<Self> {
+        let mut seen = TBitSet::new();
+        for &new in mapping.iter() {
+            if new.as_index() >= mapping.len() || seen.get(new) {
+                return None;
+            }
+            seen.add(new);
+        }
+
+        Some(Self { inner: mapping })
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &TSlice<I, I> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> TVec<I, I> {
+        self.inner
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inner = self.inner.clone();
+        for (old, &new) in self.inner.iter_enumerated() {
+            inner[new] = old;
+        }
+        Self { inner }
+    }
+
+    /// Returns the permutation which first applies `self` and then `other`.
+    ///
+    /// Panics if both permutations have a different length.
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "composing permutations of different lengths"
+        );
+        Self {
+            inner: self.inner.iter().map(|&mid| other.inner[mid]).collect(),
+        }
+    }
+
+    /// Moves the element at each `old` index to `self[old]` by following
+    /// the cycles of this permutation.
+    ///
+    /// Panics if `slice` and `self` have a different length.
+    pub fn apply_to<T>(&self, slice: &mut TSlice<I, T>) {
+        assert_eq!(
+            self.len(),
+            slice.len(),
+            "applying a permutation to a slice of different length"
+        );
+        let mut done = TBitSet::new();
+        for start in self.inner.index_iter() {
+            if done.get(start) {
+                continue;
+            }
+
+            let mut next = self.inner[start];
+            while next != start {
+                slice.swap(start, next);
+                done.add(next);
+                next = self.inner[next];
+            }
+            done.add(start);
+        }
+    }
+
+    /// Replaces each index `old` in `set` with `self[old]`.
+    ///
+    /// Panics if `set` contains an index which is out of bounds for `self`.
+    pub fn apply_to_bitset(&self, set: &mut TBitSet<I>) {
+        *set = set.iter().map(|old| self.inner[old]).collect();
+    }
+}
+
+impl<I: TIndex> Index<I> for Permutation<I> {
+    type Output = I;
+
+    fn index(&self, old: I) -> &I {
+        &self.inner[old]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tvec;
+
+    #[test]
+    fn new() {
+        assert!(Permutation::<u32>::new(tvec![1, 2, 0]).is_some());
+        assert!(Permutation::<u32>::new(tvec![1, 1, 0]).is_none());
+        assert!(Permutation::<u32>::new(tvec![1, 3, 0]).is_none());
+        assert_eq!(Permutation::<u32>::identity(3).into_inner(), tvec![0, 1, 2]);
+    }
+
+    #[test]
+    fn apply() {
+        let perm = Permutation::<u32>::new(tvec![2, 0, 1, 4, 3, 5]).unwrap();
+        let mut v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd', 'e', 'f'];
+        perm.apply_to(&mut v);
+        assert_eq!(v, tvec!['b', 'c', 'a', 'e', 'd', 'f']);
+        perm.inverse().apply_to(&mut v);
+        assert_eq!(v, tvec!['a', 'b', 'c', 'd', 'e', 'f']);
+
+        let mut set: TBitSet<u32> = [0, 3, 5].into_iter().collect();
+        perm.apply_to_bitset(&mut set);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![2, 4, 5]);
+    }
+
+    #[test]
+    fn compose() {
+        let a = Permutation::<u32>::new(tvec![1, 2, 0]).unwrap();
+        let b = Permutation::<u32>::new(tvec![0, 2, 1]).unwrap();
+        let ab = a.compose(&b);
+
+        let mut x: TVec<u32, u8> = tvec![10, 11, 12];
+        let mut y = x.clone();
+        a.apply_to(&mut x);
+        b.apply_to(&mut x);
+        ab.apply_to(&mut y);
+        assert_eq!(x, y);
+        assert_eq!(a.compose(&a.inverse()), Permutation::identity(3));
+        assert_eq!(ab[0], 2);
+    }
+}
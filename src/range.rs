@@ -0,0 +1,199 @@
+use std::{
+    cmp,
+    iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator},
+    marker::PhantomData,
+    ops::Range,
+};
+
+use crate::TIndex;
+
+/// A half-open range of indices, `start..end`.
+///
+/// The bounds are stored as `usize`, so `end` may be one past the
+/// largest index representable by `I`.
+#[derive(Debug)]
+pub struct IndexRange<I> {
+    _marker: PhantomData<fn(I)>,
+    inner: Range<usize>,
+}
+
+impl<I> IndexRange<I> {
+    pub(crate) fn from_usize(start: usize, end: usize) -> Self {
+        Self {
+            _marker: PhantomData,
+            inner: start..end,
+        }
+    }
+
+    pub(crate) fn as_usize(&self) -> Range<usize> {
+        self.inner.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<I: TIndex> IndexRange<I> {
+    pub fn new(start: I, end: I) -> Self {
+        Self::from_usize(start.as_index(), end.as_index())
+    }
+
+    pub fn start(&self) -> I {
+        I::from_index(self.inner.start)
+    }
+
+    /// Returns the exclusive end of this range.
+    ///
+    /// Panics if the end is not representable by `I`.
+    pub fn end(&self) -> I {
+        I::from_index(self.inner.end)
+    }
+
+    pub fn contains(&self, idx: I) -> bool {
+        self.inner.contains(&idx.as_index())
+    }
+
+    /// Splits this range into `start..mid` and `mid..end`.
+    ///
+    /// Panics if `mid` is not in `start..=end`.
+    pub fn split_at(&self, mid: I) -> (Self, Self) {
+        let mid = mid.as_index();
+        assert!(
+            self.inner.start <= mid && mid <= self.inner.end,
+            "`mid` out of bounds in `IndexRange::split_at`"
+        );
+        (
+            Self::from_usize(self.inner.start, mid),
+            Self::from_usize(mid, self.inner.end),
+        )
+    }
+
+    /// Returns the indices contained in both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let start = cmp::max(self.inner.start, other.inner.start);
+        let end = cmp::min(self.inner.end, other.inner.end);
+        Self::from_usize(start, cmp::max(start, end))
+    }
+}
+
+impl<I> Clone for IndexRange<I> {
+    fn clone(&self) -> Self {
+        Self {
+            _marker: PhantomData,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I> PartialEq for IndexRange<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<I> Eq for IndexRange<I> {}
+
+impl<I> IndexRange<I> {
+    /// Reinterprets `self` as a range of `J`, see [`TVec::cast_index`](crate::TVec::cast_index).
+    pub fn cast_index<J>(self) -> IndexRange<J> {
+        IndexRange::from_usize(self.inner.start, self.inner.end)
+    }
+}
+
+impl<I: TIndex> From<Range<I>> for IndexRange<I> {
+    fn from(range: Range<I>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<I: TIndex> Iterator for IndexRange<I> {
+    type Item = I;
+
+    #[inline]
+    fn next(&mut self) -> Option<I> {
+        self.inner.next().map(I::from_index)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<I> {
+        self.inner.nth(n).map(I::from_index)
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<I> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<I: TIndex> DoubleEndedIterator for IndexRange<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<I> {
+        self.inner.next_back().map(I::from_index)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<I> {
+        self.inner.nth_back(n).map(I::from_index)
+    }
+}
+
+impl<I: TIndex> ExactSizeIterator for IndexRange<I> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<I: TIndex> FusedIterator for IndexRange<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tvec, TVec};
+
+    #[test]
+    fn range() {
+        let range = IndexRange::new(2u32, 6);
+        assert_eq!(range.len(), 4);
+        assert!(range.contains(5));
+        assert!(!range.contains(6));
+        assert_eq!(range.clone().rev().collect::<Vec<_>>(), vec![5, 4, 3, 2]);
+
+        let (left, right) = range.split_at(3);
+        assert_eq!(left, IndexRange::new(2, 3));
+        assert_eq!(right, IndexRange::new(3, 6));
+        assert_eq!(
+            right.intersect(&IndexRange::new(0, 4)),
+            IndexRange::new(3, 4)
+        );
+        assert!(left.intersect(&IndexRange::new(5, 9)).is_empty());
+        assert_eq!(right.clone().nth(1), Some(4));
+        assert_eq!(right.last(), Some(5));
+    }
+
+    #[test]
+    fn slice_index() {
+        let v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd'];
+        let range = v.index_range();
+        assert_eq!(range, IndexRange::new(0, 4));
+        let (_, tail) = range.split_at(1);
+        assert_eq!(v[tail.clone()].to_slice(), &['b', 'c', 'd']);
+        assert_eq!(tail.map(|i| v[i]).collect::<String>(), "bcd");
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_at_out_of_bounds() {
+        IndexRange::new(2u32, 6).split_at(7);
+    }
+}
@@ -0,0 +1,196 @@
+use std::{cmp::Ordering, fmt, ops::Index};
+
+use crate::{TBitSet, TIndex, TSlice, TVec};
+
+/// Maps the indices of a vector before some modification to the indices
+/// after it, with `None` for removed elements.
+///
+/// Returned by `TVec::retain_remap`, `TVec::remove_remap` and `TVec::insert_remap`.
+pub struct IndexRemap<I> {
+    inner: TVec<I, Option<I>>,
+}
+
+impl<I: Clone> Clone for IndexRemap<I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I: PartialEq> PartialEq for IndexRemap<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<I: Eq> Eq for IndexRemap<I> {}
+
+impl<I: fmt::Debug> fmt::Debug for IndexRemap<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IndexRemap").field(&self.inner).finish()
+    }
+}
+
+impl<I: TIndex> IndexRemap<I> {
+    pub fn identity(len: usize) -> Self {
+        Self {
+            inner: (0..len).map(|i| Some(I::from_index(i))).collect(),
+        }
+    }
+
+    /// Creates a remap moving the element at `old` to `mapping[old]`.
+    pub fn new(mapping: TVec<I, Option<I>>) -> Self {
+        Self { inner: mapping }
+    }
+
+    /// The length of the vector before the modification.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &TSlice<I, Option<I>> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> TVec<I, Option<I>> {
+        self.inner
+    }
+
+    /// Returns the new index of `old`, or `None` if it has been removed.
+    ///
+    /// Panics if `old` is out of bounds.
+    pub fn apply(&self, old: I) -> Option<I> {
+        self.inner[old]
+    }
+
+    /// Updates each id in `ids`.
+    ///
+    /// Panics if `ids` contains an id which has been removed.
+    pub fn apply_to_slice<J: TIndex>(&self, ids: &mut TSlice<J, I>) {
+        for id in ids.iter_mut() {
+            *id = self.inner[*id].expect("remapping a removed index");
+        }
+    }
+
+    /// Updates each id in `ids`, replacing removed ids with `None`.
+    pub fn apply_to_optional_slice<J: TIndex>(&self, ids: &mut TSlice<J, Option<I>>) {
+        for id in ids.iter_mut() {
+            *id = id.and_then(|old| self.inner[old]);
+        }
+    }
+
+    /// Updates each id in `set`, dropping removed ids.
+    pub fn apply_to_bitset(&self, set: &mut TBitSet<I>) {
+        *set = set.iter().filter_map(|old| self.inner[old]).collect();
+    }
+}
+
+impl<I: TIndex> Index<I> for IndexRemap<I> {
+    type Output = Option<I>;
+
+    fn index(&self, old: I) -> &Option<I> {
+        &self.inner[old]
+    }
+}
+
+impl<I: TIndex, T> TVec<I, T> {
+    /// Like `retain`, but also returns where each element has been moved to.
+    pub fn retain_remap<F>(&mut self, mut f: F) -> IndexRemap<I>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut next = 0;
+        let mut remap = TVec::with_capacity(self.len());
+        self.retain(|elem| {
+            if f(elem) {
+                remap.push(Some(I::from_index(next)));
+                next += 1;
+                true
+            } else {
+                remap.push(None);
+                false
+            }
+        });
+        IndexRemap::new(remap)
+    }
+
+    /// Like `remove`, but also returns where each element has been moved to.
+    pub fn remove_remap(&mut self, id: I) -> (T, IndexRemap<I>) {
+        let elem = self.remove(id);
+        let idx = id.as_index();
+        let remap = (0..=self.len())
+            .map(|old| match old.cmp(&idx) {
+                Ordering::Less => Some(I::from_index(old)),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(I::from_index(old - 1)),
+            })
+            .collect();
+        (elem, IndexRemap::new(remap))
+    }
+
+    /// Like `insert`, but also returns where each element has been moved to.
+    pub fn insert_remap(&mut self, id: I, elem: T) -> IndexRemap<I> {
+        self.insert(id, elem);
+        let idx = id.as_index();
+        let remap = (0..self.len() - 1)
+            .map(|old| Some(I::from_index(if old < idx { old } else { old + 1 })))
+            .collect();
+        IndexRemap::new(remap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tvec;
+
+    #[test]
+    fn retain() {
+        let mut v: TVec<u32, char> = tvec!['a', 'b', 'c', 'd', 'e'];
+        let remap = v.retain_remap(|c| !matches!(c, 'b' | 'd'));
+        assert_eq!(v, tvec!['a', 'c', 'e']);
+        assert_eq!(
+            remap.as_slice().to_slice(),
+            &[Some(0), None, Some(1), None, Some(2)]
+        );
+
+        let mut refs: TVec<u8, u32> = tvec![4, 2, 0];
+        remap.apply_to_slice(&mut refs);
+        assert_eq!(refs, tvec![2, 1, 0]);
+
+        let mut opt: TVec<u8, Option<u32>> = tvec![Some(1), None, Some(4)];
+        remap.apply_to_optional_slice(&mut opt);
+        assert_eq!(opt, tvec![None, None, Some(2)]);
+
+        let mut set: TBitSet<u32> = [0, 1, 4].into_iter().collect();
+        remap.apply_to_bitset(&mut set);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    fn remove_insert() {
+        let mut v: TVec<u32, char> = tvec!['a', 'b', 'c'];
+        let (c, remap) = v.remove_remap(1);
+        assert_eq!(c, 'b');
+        assert_eq!(remap.as_slice().to_slice(), &[Some(0), None, Some(1)]);
+
+        let remap = v.insert_remap(0, 'x');
+        assert_eq!(v, tvec!['x', 'a', 'c']);
+        assert_eq!(remap.apply(0), Some(1));
+        assert_eq!(remap[1], Some(2));
+        assert_eq!(remap.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_removed() {
+        let mut v: TVec<u32, u8> = tvec![1, 2];
+        let (_, remap) = v.remove_remap(0);
+        remap.apply_to_slice::<u32>(&mut tvec![0]);
+    }
+}
@@ -1,6 +1,8 @@
-use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
 
-use crate::{TIndex, TSlice};
+use crate::{IndexRange, TIndex, TSlice};
 
 mod private {
     use super::*;
@@ -9,11 +11,13 @@ mod private {
 
     impl<I: TIndex> Sealed for I {}
     impl<I: TIndex> Sealed for Range<I> {}
+    impl<I: TIndex> Sealed for IndexRange<I> {}
     impl<I: TIndex> Sealed for RangeFrom<I> {}
     impl Sealed for RangeFull {}
     impl<I: TIndex> Sealed for RangeInclusive<I> {}
     impl<I: TIndex> Sealed for RangeTo<I> {}
     impl<I: TIndex> Sealed for RangeToInclusive<I> {}
+    impl<I: TIndex> Sealed for (Bound<I>, Bound<I>) {}
 }
 
 pub trait TSliceIndex<T: ?Sized>: private::Sealed {
@@ -35,6 +39,45 @@ pub trait TSliceIndex<T: ?Sized>: private::Sealed {
     /// Returns a mutable reference to the output at this location, panicking
     /// if out of bounds.
     fn index_mut(self, slice: &mut T) -> &mut Self::Output;
+
+    /// Returns a shared reference to the output at this location, without
+    /// performing any bounds checking outside of debug assertions.
+    ///
+    /// # Safety
+    ///
+    /// `self` has to be in bounds of `slice`.
+    unsafe fn get_unchecked(self, slice: &T) -> &Self::Output;
+
+    /// Returns a mutable reference to the output at this location, without
+    /// performing any bounds checking outside of debug assertions.
+    ///
+    /// # Safety
+    ///
+    /// `self` has to be in bounds of `slice`.
+    unsafe fn get_unchecked_mut(self, slice: &mut T) -> &mut Self::Output;
+}
+
+/// Asserts that `range` is in bounds of a slice of length `len`
+/// if debug assertions are enabled.
+#[inline]
+fn debug_check_range(range: Range<usize>, len: usize) -> Range<usize> {
+    debug_assert!(
+        range.start <= range.end && range.end <= len,
+        "range {:?} out of bounds for slice of length {}",
+        range,
+        len
+    );
+    range
+}
+
+/// Converts the bounds of `range` to `usize`.
+pub(crate) fn bounds_as_usize<I: TIndex, R: RangeBounds<I>>(
+    range: &R,
+) -> (Bound<usize>, Bound<usize>) {
+    (
+        range.start_bound().map(|&i| i.as_index()),
+        range.end_bound().map(|&i| i.as_index()),
+    )
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for I {
@@ -55,6 +98,30 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for I {
     fn index_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
         &mut slice.inner[self.as_index()]
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let idx = self.as_index();
+        debug_assert!(
+            idx < slice.inner.len(),
+            "index {} out of bounds for slice of length {}",
+            idx,
+            slice.inner.len()
+        );
+        // SAFETY: the caller guarantees that `idx` is in bounds
+        unsafe { slice.inner.get_unchecked(idx) }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let idx = self.as_index();
+        debug_assert!(
+            idx < slice.inner.len(),
+            "index {} out of bounds for slice of length {}",
+            idx,
+            slice.inner.len()
+        );
+        // SAFETY: the caller guarantees that `idx` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(idx) }
+    }
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for Range<I> {
@@ -95,6 +162,56 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for Range<I> {
         }])
             .into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(
+            self.start.as_index()..self.end.as_index(),
+            slice.inner.len(),
+        );
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(
+            self.start.as_index()..self.end.as_index(),
+            slice.inner.len(),
+        );
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
+}
+
+impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for IndexRange<I> {
+    type Output = TSlice<I, T>;
+
+    fn get(self, slice: &TSlice<I, T>) -> Option<&Self::Output> {
+        slice.inner.get(self.as_usize()).map(|s| s.into())
+    }
+
+    fn get_mut(self, slice: &mut TSlice<I, T>) -> Option<&mut Self::Output> {
+        slice.inner.get_mut(self.as_usize()).map(|s| s.into())
+    }
+
+    fn index(self, slice: &TSlice<I, T>) -> &Self::Output {
+        slice.inner[self.as_usize()].into()
+    }
+
+    fn index_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        (&mut slice.inner[self.as_usize()]).into()
+    }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(self.as_usize(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(self.as_usize(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeFrom<I> {
@@ -131,6 +248,18 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeFrom<I> {
         }])
             .into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(self.start.as_index()..slice.inner.len(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(self.start.as_index()..slice.inner.len(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeFull {
@@ -151,6 +280,14 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeFull {
     fn index_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
         (&mut slice.inner[self]).into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        self.index(slice)
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        self.index_mut(slice)
+    }
 }
 
 fn range_inclusive<I: TIndex>(range: RangeInclusive<I>) -> RangeInclusive<usize> {
@@ -176,6 +313,20 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeInclusive<I> {
     fn index_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
         (&mut slice.inner[range_inclusive(self)]).into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let (start, end) = self.into_inner();
+        let range = debug_check_range(start.as_index()..end.as_index() + 1, slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let (start, end) = self.into_inner();
+        let range = debug_check_range(start.as_index()..end.as_index() + 1, slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeTo<I> {
@@ -212,6 +363,18 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeTo<I> {
         }])
             .into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(0..self.end.as_index(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(0..self.end.as_index(), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
 }
 
 impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeToInclusive<I> {
@@ -248,4 +411,66 @@ impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for RangeToInclusive<I> {
         }])
             .into()
     }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(0..self.end.as_index() + 1, slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(0..self.end.as_index() + 1, slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
+}
+
+impl<I: TIndex, T> TSliceIndex<TSlice<I, T>> for (Bound<I>, Bound<I>) {
+    type Output = TSlice<I, T>;
+
+    fn get(self, slice: &TSlice<I, T>) -> Option<&Self::Output> {
+        slice.inner.get(bounds_as_usize(&self)).map(|s| s.into())
+    }
+
+    fn get_mut(self, slice: &mut TSlice<I, T>) -> Option<&mut Self::Output> {
+        slice
+            .inner
+            .get_mut(bounds_as_usize(&self))
+            .map(|s| s.into())
+    }
+
+    fn index(self, slice: &TSlice<I, T>) -> &Self::Output {
+        slice.inner[bounds_as_usize(&self)].into()
+    }
+
+    fn index_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        (&mut slice.inner[bounds_as_usize(&self)]).into()
+    }
+
+    unsafe fn get_unchecked(self, slice: &TSlice<I, T>) -> &Self::Output {
+        let range = debug_check_range(bounds_to_range(&self, slice.inner.len()), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked(range).into() }
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: &mut TSlice<I, T>) -> &mut Self::Output {
+        let range = debug_check_range(bounds_to_range(&self, slice.inner.len()), slice.inner.len());
+        // SAFETY: the caller guarantees that `range` is in bounds
+        unsafe { slice.inner.get_unchecked_mut(range).into() }
+    }
+}
+
+/// Converts a pair of bounds to a `Range<usize>` for a slice of length `len`.
+fn bounds_to_range<I: TIndex>(bounds: &(Bound<I>, Bound<I>), len: usize) -> Range<usize> {
+    let start = match bounds.0 {
+        Bound::Included(start) => start.as_index(),
+        Bound::Excluded(start) => start.as_index() + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match bounds.1 {
+        Bound::Included(end) => end.as_index() + 1,
+        Bound::Excluded(end) => end.as_index(),
+        Bound::Unbounded => len,
+    };
+    start..end
 }